@@ -0,0 +1,77 @@
+//! Waiting for the competition status to change.
+//!
+//! The reactor checks the competition status on every tick and only wakes tasks once it has
+//! changed, so waiting for a change doesn't require polling the status in a loop.
+//!
+//! This module works with the raw status bits returned by the SDK. Most code should use the
+//! typed futures in `vexide_devices::competition` instead.
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use vex_sdk::vexCompetitionStatus;
+
+use crate::executor::EXECUTOR;
+
+/// Gets the raw competition status bits.
+pub fn status() -> u32 {
+    unsafe { vexCompetitionStatus() }
+}
+
+/// Waits until the raw competition status is different from `from`, returning the new status.
+///
+/// This completes immediately if the status is already different.
+pub const fn status_changed(from: u32) -> StatusChangedFuture {
+    StatusChangedFuture {
+        from,
+        watcher: None,
+    }
+}
+
+/// A future that completes once the raw competition status changes.
+///
+/// Created by [`status_changed`]. Dropping the future before it completes removes it from the
+/// reactor.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct StatusChangedFuture {
+    from: u32,
+    watcher: Option<u64>,
+}
+
+impl Future for StatusChangedFuture {
+    type Output = u32;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let status = status();
+        if status != self.from {
+            if let Some(watcher) = self.watcher.take() {
+                EXECUTOR.with_reactor(|reactor| reactor.competition.remove(watcher));
+            }
+            return Poll::Ready(status);
+        }
+
+        let from = self.from;
+        let watcher = self.watcher;
+        self.watcher = Some(EXECUTOR.with_reactor(|reactor| match watcher {
+            Some(watcher) => {
+                reactor.competition.update(watcher, from, cx.waker());
+                watcher
+            }
+            None => reactor.competition.insert(cx.waker().clone(), from),
+        }));
+
+        Poll::Pending
+    }
+}
+
+impl Drop for StatusChangedFuture {
+    fn drop(&mut self) {
+        if let Some(watcher) = self.watcher.take() {
+            EXECUTOR.with_reactor(|reactor| reactor.competition.remove(watcher));
+        }
+    }
+}
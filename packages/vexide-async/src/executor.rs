@@ -50,10 +50,8 @@ impl Executor {
     /// Run the provided closure with the reactor.
     /// Used to ensure the thread safety of the executor.
     /// The closure is run with interrupts disabled.
-    pub(crate) fn with_reactor(&self, f: impl FnOnce(&mut Reactor)) {
-        critical_section::with(|_| {
-            f(&mut self.reactor.borrow_mut());
-        });
+    pub(crate) fn with_reactor<R>(&self, f: impl FnOnce(&mut Reactor) -> R) -> R {
+        critical_section::with(|_| f(&mut self.reactor.borrow_mut()))
     }
 
     pub(crate) fn tick(&self) -> bool {
@@ -8,11 +8,17 @@
 
 extern crate alloc;
 
+#[cfg(not(any(target_arch = "arm", target_arch = "wasm32")))]
+extern crate vexide_sim as vex_sdk;
+
 use core::{future::Future, task::Poll};
 
 use async_task::Task;
 use executor::EXECUTOR;
+use reactor::TimerKey;
+use vexide_core::time::Instant;
 
+pub mod competition;
 mod executor;
 mod reactor;
 
@@ -30,36 +36,61 @@ pub fn block_on<F: Future + 'static>(future: F) -> F::Output {
 }
 
 /// A future that will complete after the given duration.
-/// Sleep futures that are closer to completion are prioritized to improve accuracy.
+///
+/// Any number of sleep futures may share the same deadline. The reactor wakes every expired
+/// sleeper each time the executor ticks, with the microsecond resolution of [`Instant`].
+/// Dropping a sleep future before it completes removes it from the reactor.
 #[derive(Debug)]
 pub struct SleepFuture {
-    target_millis: u32,
+    deadline: Instant,
+    timer: Option<TimerKey>,
 }
+
 impl Future for SleepFuture {
     type Output = ();
 
     fn poll(
-        self: core::pin::Pin<&mut Self>,
+        mut self: core::pin::Pin<&mut Self>,
         cx: &mut core::task::Context<'_>,
     ) -> core::task::Poll<Self::Output> {
-        if self.target_millis < unsafe { (vex_sdk::vexSystemHighResTimeGet() / 1000) as _ } {
-            Poll::Ready(())
-        } else {
-            EXECUTOR.with_reactor(|reactor| {
-                reactor
-                    .sleepers
-                    .push(cx.waker().clone(), self.target_millis)
-            });
-            Poll::Pending
+        if Instant::now() >= self.deadline {
+            if let Some(timer) = self.timer.take() {
+                EXECUTOR.with_reactor(|reactor| reactor.sleepers.remove(timer));
+            }
+            return Poll::Ready(());
+        }
+
+        let deadline = self.deadline;
+        let timer = self.timer;
+        self.timer = Some(EXECUTOR.with_reactor(|reactor| match timer {
+            Some(timer) => {
+                reactor.sleepers.update(timer, cx.waker());
+                timer
+            }
+            None => reactor.sleepers.insert(cx.waker().clone(), deadline),
+        }));
+
+        Poll::Pending
+    }
+}
+
+impl Drop for SleepFuture {
+    fn drop(&mut self) {
+        if let Some(timer) = self.timer.take() {
+            EXECUTOR.with_reactor(|reactor| reactor.sleepers.remove(timer));
         }
     }
 }
 
 /// Returns a future that will complete after the given duration.
 pub fn sleep(duration: core::time::Duration) -> SleepFuture {
+    sleep_until(Instant::now() + duration)
+}
+
+/// Returns a future that will complete once the given instant has been reached.
+pub const fn sleep_until(deadline: Instant) -> SleepFuture {
     SleepFuture {
-        target_millis: unsafe {
-            (vex_sdk::vexSystemHighResTimeGet() / 1000) as u32 + duration.as_millis() as u32
-        },
+        deadline,
+        timer: None,
     }
 }
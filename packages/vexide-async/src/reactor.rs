@@ -1,36 +1,212 @@
-use alloc::collections::BTreeMap;
+use alloc::{collections::BTreeMap, vec::Vec};
 use core::task::Waker;
 
+use vex_sdk::vexCompetitionStatus;
+use vexide_core::time::Instant;
+
+/// A handle to a timer registered with [`Sleepers`].
+///
+/// Timers are ordered by their deadline, and timers with the same deadline are ordered by
+/// registration, so every registered timer has a unique key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimerKey {
+    deadline: Instant,
+    id: u64,
+}
+
+/// Wakers waiting for a deadline to pass.
 pub struct Sleepers {
-    sleepers: BTreeMap<u32, Waker>,
+    sleepers: BTreeMap<TimerKey, Waker>,
+    next_id: u64,
 }
 
 impl Sleepers {
-    pub fn push(&mut self, waker: Waker, target: u32) {
-        self.sleepers.insert(target, waker);
+    const fn new() -> Self {
+        Self {
+            sleepers: BTreeMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Registers a waker to be woken once `deadline` has passed.
+    pub fn insert(&mut self, waker: Waker, deadline: Instant) -> TimerKey {
+        let key = TimerKey {
+            deadline,
+            id: self.next_id,
+        };
+        self.next_id += 1;
+
+        self.sleepers.insert(key, waker);
+        key
+    }
+
+    /// Replaces the waker of a registered timer, re-registering it if it has already fired.
+    pub fn update(&mut self, key: TimerKey, waker: &Waker) {
+        match self.sleepers.get_mut(&key) {
+            Some(existing) => existing.clone_from(waker),
+            None => {
+                self.sleepers.insert(key, waker.clone());
+            }
+        }
+    }
+
+    /// Deregisters a timer, returning its waker if it has not fired yet.
+    pub fn remove(&mut self, key: TimerKey) -> Option<Waker> {
+        self.sleepers.remove(&key)
+    }
+
+    /// Removes every timer whose deadline is at or before `now`, returning their wakers.
+    pub fn take_expired(&mut self, now: Instant) -> Vec<Waker> {
+        let mut expired = Vec::new();
+
+        while let Some(entry) = self.sleepers.first_entry() {
+            if entry.key().deadline > now {
+                break;
+            }
+            expired.push(entry.remove());
+        }
+
+        expired
+    }
+}
+
+/// Wakers waiting for the competition status to change.
+pub struct CompetitionWatchers {
+    /// Each watcher's waker, along with the raw status it is waiting to change from.
+    watchers: BTreeMap<u64, (u32, Waker)>,
+    next_id: u64,
+}
+
+impl CompetitionWatchers {
+    const fn new() -> Self {
+        Self {
+            watchers: BTreeMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Registers a waker to be woken once the competition status differs from `from`.
+    pub fn insert(&mut self, waker: Waker, from: u32) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.watchers.insert(id, (from, waker));
+        id
     }
 
-    pub fn pop(&mut self) -> Option<Waker> {
-        self.sleepers.pop_first().map(|(_, waker)| waker)
+    /// Replaces the waker of a registered watcher, re-registering it if it has already fired.
+    pub fn update(&mut self, id: u64, from: u32, waker: &Waker) {
+        match self.watchers.get_mut(&id) {
+            Some((_, existing)) => existing.clone_from(waker),
+            None => {
+                self.watchers.insert(id, (from, waker.clone()));
+            }
+        }
+    }
+
+    /// Deregisters a watcher, returning its waker if it has not fired yet.
+    pub fn remove(&mut self, id: u64) -> Option<Waker> {
+        self.watchers.remove(&id).map(|(_, waker)| waker)
+    }
+
+    /// Removes every watcher waiting for the status to change from something other than
+    /// `status`, returning their wakers.
+    pub fn take_changed(&mut self, status: u32) -> Vec<Waker> {
+        // This runs on every tick, so nothing is allocated unless the status has changed.
+        let changed: Vec<u64> = self
+            .watchers
+            .iter()
+            .filter(|(_, (from, _))| *from != status)
+            .map(|(id, _)| *id)
+            .collect();
+
+        changed
+            .into_iter()
+            .filter_map(|id| self.remove(id))
+            .collect()
     }
 }
 
 pub struct Reactor {
     pub(crate) sleepers: Sleepers,
+    pub(crate) competition: CompetitionWatchers,
 }
 
 impl Reactor {
     pub const fn new() -> Self {
         Self {
-            sleepers: Sleepers {
-                sleepers: BTreeMap::new(),
-            },
+            sleepers: Sleepers::new(),
+            competition: CompetitionWatchers::new(),
         }
     }
 
+    /// Wakes every timer that has expired and every watcher whose competition status has
+    /// changed.
     pub fn tick(&mut self) {
-        if let Some(sleeper) = self.sleepers.pop() {
-            sleeper.wake()
+        for waker in self.sleepers.take_expired(Instant::now()) {
+            waker.wake()
+        }
+
+        if !self.competition.watchers.is_empty() {
+            let status = unsafe { vexCompetitionStatus() };
+            for waker in self.competition.take_changed(status) {
+                waker.wake()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+    use core::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use waker_fn::waker_fn;
+
+    use super::*;
+
+    fn counting_waker(count: &Arc<AtomicUsize>) -> Waker {
+        let count = count.clone();
+        waker_fn(move || {
+            count.fetch_add(1, Ordering::SeqCst);
+        })
+    }
+
+    #[test]
+    fn sleepers_with_same_deadline_are_all_woken() {
+        let woken = Arc::new(AtomicUsize::new(0));
+        let deadline = Instant::now() + Duration::from_millis(5);
+
+        let mut sleepers = Sleepers::new();
+        let first = sleepers.insert(counting_waker(&woken), deadline);
+        let second = sleepers.insert(counting_waker(&woken), deadline);
+        assert_ne!(first, second);
+
+        assert!(sleepers
+            .take_expired(deadline - Duration::from_millis(1))
+            .is_empty());
+
+        for waker in sleepers.take_expired(deadline) {
+            waker.wake();
         }
+        assert_eq!(woken.load(Ordering::SeqCst), 2);
+        assert!(sleepers.take_expired(deadline).is_empty());
+    }
+
+    #[test]
+    fn removed_sleepers_are_not_woken() {
+        let deadline = Instant::now();
+        let woken = Arc::new(AtomicUsize::new(0));
+
+        let mut sleepers = Sleepers::new();
+        let first = sleepers.insert(counting_waker(&woken), deadline);
+        sleepers.insert(counting_waker(&woken), deadline);
+
+        assert!(sleepers.remove(first).is_some());
+        assert_eq!(sleepers.take_expired(deadline).len(), 1);
+        assert!(sleepers.remove(first).is_none());
     }
 }
@@ -11,10 +11,18 @@
 
 #![no_std]
 #![feature(error_in_core)]
-#![cfg_attr(feature = "critical-section", feature(asm_experimental_arch))]
+#![cfg_attr(
+    all(feature = "critical-section", target_arch = "arm"),
+    feature(asm_experimental_arch)
+)]
+
+#[cfg(not(any(target_arch = "arm", target_arch = "wasm32")))]
+extern crate vexide_sim as vex_sdk;
+
+extern crate alloc;
 
 pub mod allocator;
-#[cfg(feature = "critical-section")]
+#[cfg(all(feature = "critical-section", target_arch = "arm"))]
 pub mod critical_section;
 pub mod io;
 pub mod sync;
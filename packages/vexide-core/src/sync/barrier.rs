@@ -0,0 +1,93 @@
+//! Task barriers.
+
+use core::cell::RefCell;
+
+use super::notify::Notify;
+
+struct BarrierState {
+    arrived: usize,
+    generation: u64,
+}
+
+/// Makes a group of tasks wait for each other to reach the same point.
+///
+/// Once `n` tasks have called [`Barrier::wait`], all of them are woken and the barrier resets
+/// so that it can be used again.
+pub struct Barrier {
+    n: usize,
+    state: critical_section::Mutex<RefCell<BarrierState>>,
+    notify: Notify,
+}
+
+impl Barrier {
+    /// Creates a barrier that releases waiting tasks once `n` of them are waiting.
+    ///
+    /// A barrier created with an `n` of 0 behaves the same as one created with an `n` of 1.
+    pub const fn new(n: usize) -> Self {
+        Self {
+            n,
+            state: critical_section::Mutex::new(RefCell::new(BarrierState {
+                arrived: 0,
+                generation: 0,
+            })),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Waits until every task in the group has reached the barrier.
+    ///
+    /// Exactly one task in each group receives a [`BarrierWaitResult`] for which
+    /// [`BarrierWaitResult::is_leader`] returns `true`.
+    ///
+    /// A task counts as having reached the barrier as soon as this future is first polled, even
+    /// if the future is later dropped.
+    pub async fn wait(&self) -> BarrierWaitResult {
+        let generation = critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+            state.arrived += 1;
+
+            if state.arrived >= self.n {
+                state.arrived = 0;
+                state.generation += 1;
+                None
+            } else {
+                Some(state.generation)
+            }
+        });
+
+        let Some(generation) = generation else {
+            self.notify.notify_all();
+            return BarrierWaitResult(true);
+        };
+
+        loop {
+            let mut notified = self.notify.notified();
+            notified.enable();
+
+            if critical_section::with(|cs| self.state.borrow_ref(cs).generation != generation) {
+                return BarrierWaitResult(false);
+            }
+
+            notified.await;
+        }
+    }
+}
+
+impl core::fmt::Debug for Barrier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Barrier")
+            .field("n", &self.n)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returned by [`Barrier::wait`] once every task in the group has reached the barrier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierWaitResult(bool);
+
+impl BarrierWaitResult {
+    /// Returns `true` for exactly one of the tasks released by the barrier.
+    pub const fn is_leader(&self) -> bool {
+        self.0
+    }
+}
@@ -0,0 +1,281 @@
+//! Multi-producer, multi-consumer channels where every receiver sees every message.
+//!
+//! A broadcast channel is created with [`channel`]. Each message is cloned for every receiver
+//! that receives it. The channel holds a fixed number of messages, and sending never waits: if
+//! a receiver falls too far behind, the oldest messages are overwritten and the receiver is
+//! told how many it missed with [`RecvError::Lagged`].
+
+use alloc::{collections::VecDeque, sync::Arc};
+use core::{cell::RefCell, fmt};
+
+use snafu::Snafu;
+
+use super::notify::Notify;
+
+struct State<T> {
+    /// Messages that have not been overwritten yet, oldest first.
+    buffer: VecDeque<T>,
+    capacity: usize,
+    /// The sequence number of the next message that will be sent.
+    next_seq: u64,
+    senders: usize,
+    receivers: usize,
+}
+
+impl<T> State<T> {
+    /// The sequence number of the oldest message still in the buffer.
+    fn oldest_seq(&self) -> u64 {
+        self.next_seq - self.buffer.len() as u64
+    }
+}
+
+struct Shared<T> {
+    state: critical_section::Mutex<RefCell<State<T>>>,
+    /// Notified when a message is sent or the last sender is dropped.
+    notify: Notify,
+}
+
+/// Creates a broadcast channel that holds up to `capacity` messages.
+///
+/// # Panics
+///
+/// Panics if `capacity` is 0.
+pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(
+        capacity > 0,
+        "broadcast channel capacity must be greater than 0"
+    );
+
+    let shared = Arc::new(Shared {
+        state: critical_section::Mutex::new(RefCell::new(State {
+            buffer: VecDeque::with_capacity(capacity),
+            capacity,
+            next_seq: 0,
+            senders: 1,
+            receivers: 1,
+        })),
+        notify: Notify::new(),
+    });
+
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared, next: 0 },
+    )
+}
+
+/// The sending half of a broadcast channel.
+///
+/// Senders can be cloned to send messages from several tasks.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Clone> Sender<T> {
+    /// Sends a message to every receiver, returning the number of receivers.
+    ///
+    /// Returns the message back as an error if there are no receivers.
+    pub fn send(&self, value: T) -> Result<usize, SendError<T>> {
+        let receivers = critical_section::with(|cs| {
+            let mut state = self.shared.state.borrow_ref_mut(cs);
+            if state.receivers == 0 {
+                return Err(SendError(value));
+            }
+
+            if state.buffer.len() == state.capacity {
+                state.buffer.pop_front();
+            }
+            state.buffer.push_back(value);
+            state.next_seq += 1;
+
+            Ok(state.receivers)
+        })?;
+
+        self.shared.notify.notify_all();
+        Ok(receivers)
+    }
+
+    /// Creates a new receiver that will receive every message sent after this call.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let next = critical_section::with(|cs| {
+            let mut state = self.shared.state.borrow_ref_mut(cs);
+            state.receivers += 1;
+            state.next_seq
+        });
+
+        Receiver {
+            shared: self.shared.clone(),
+            next,
+        }
+    }
+
+    /// Returns the number of receivers.
+    pub fn receiver_count(&self) -> usize {
+        critical_section::with(|cs| self.shared.state.borrow_ref(cs).receivers)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        critical_section::with(|cs| self.shared.state.borrow_ref_mut(cs).senders += 1);
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let last = critical_section::with(|cs| {
+            let mut state = self.shared.state.borrow_ref_mut(cs);
+            state.senders -= 1;
+            state.senders == 0
+        });
+
+        if last {
+            self.shared.notify.notify_all();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// The receiving half of a broadcast channel.
+///
+/// Cloning a receiver creates another receiver at the same position in the channel.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    /// The sequence number of the next message this receiver will receive.
+    next: u64,
+}
+
+impl<T: Clone> Receiver<T> {
+    /// Receives the next message, waiting until one is sent.
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        let shared = self.shared.clone();
+        loop {
+            let mut sent = shared.notify.notified();
+            sent.enable();
+
+            match self.try_recv() {
+                Ok(value) => return Ok(value),
+                Err(TryRecvError::Closed) => return Err(RecvError::Closed),
+                Err(TryRecvError::Lagged { skipped }) => return Err(RecvError::Lagged { skipped }),
+                Err(TryRecvError::Empty) => {}
+            }
+
+            sent.await;
+        }
+    }
+
+    /// Attempts to receive the next message without waiting.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        critical_section::with(|cs| {
+            let state = self.shared.state.borrow_ref(cs);
+
+            let oldest = state.oldest_seq();
+            if self.next < oldest {
+                let skipped = oldest - self.next;
+                self.next = oldest;
+                return Err(TryRecvError::Lagged { skipped });
+            }
+
+            match state.buffer.get((self.next - oldest) as usize) {
+                Some(value) => {
+                    self.next += 1;
+                    Ok(value.clone())
+                }
+                None if state.senders == 0 => Err(TryRecvError::Closed),
+                None => Err(TryRecvError::Empty),
+            }
+        })
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Returns the number of messages this receiver has not received yet.
+    pub fn len(&self) -> usize {
+        critical_section::with(|cs| {
+            let state = self.shared.state.borrow_ref(cs);
+            (state.next_seq - self.next.max(state.oldest_seq())) as usize
+        })
+    }
+
+    /// Returns `true` if this receiver has received every message in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        critical_section::with(|cs| self.shared.state.borrow_ref_mut(cs).receivers += 1);
+        Self {
+            shared: self.shared.clone(),
+            next: self.next,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        critical_section::with(|cs| self.shared.state.borrow_ref_mut(cs).receivers -= 1);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+/// Returned by [`Sender::send`] when there are no receivers. Contains the message that could
+/// not be sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the channel has no receivers")
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for SendError<T> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Snafu)]
+/// Errors returned by [`Receiver::recv`].
+pub enum RecvError {
+    /// Every sender has been dropped and there are no more messages to receive.
+    Closed,
+    /// The receiver fell behind and messages were overwritten before it received them.
+    ///
+    /// The next call to receive returns the oldest message still in the channel.
+    #[snafu(display("the receiver skipped {skipped} messages"))]
+    Lagged {
+        /// The number of messages that were skipped.
+        skipped: u64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Snafu)]
+#[snafu(module)]
+/// Errors returned by [`Receiver::try_recv`].
+pub enum TryRecvError {
+    /// There are no new messages.
+    Empty,
+    /// Every sender has been dropped and there are no more messages to receive.
+    Closed,
+    /// The receiver fell behind and messages were overwritten before it received them.
+    ///
+    /// The next call to receive returns the oldest message still in the channel.
+    #[snafu(display("the receiver skipped {skipped} messages"))]
+    Lagged {
+        /// The number of messages that were skipped.
+        skipped: u64,
+    },
+}
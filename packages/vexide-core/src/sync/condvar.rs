@@ -0,0 +1,62 @@
+//! Condition variables.
+
+use super::{mutex::MutexGuard, notify::Notify};
+
+/// A condition variable for waiting on changes to data protected by a [`Mutex`](super::Mutex).
+///
+/// As with condition variables in the standard library, waiting tasks may be woken spuriously,
+/// so the condition being waited on should always be re-checked. [`Condvar::wait_while`] does
+/// this automatically.
+#[derive(Debug, Default)]
+pub struct Condvar {
+    notify: Notify,
+}
+
+impl Condvar {
+    /// Creates a new condition variable.
+    pub const fn new() -> Self {
+        Self {
+            notify: Notify::new(),
+        }
+    }
+
+    /// Unlocks the mutex and waits for a notification, then locks the mutex again.
+    ///
+    /// The task is queued for notifications before the mutex is unlocked, so a notification
+    /// sent by another task as soon as it acquires the lock is never missed.
+    pub async fn wait<'a, T>(&self, guard: MutexGuard<'a, T>) -> MutexGuard<'a, T> {
+        let mutex = guard.mutex();
+
+        let mut notified = self.notify.notified();
+        notified.enable();
+        drop(guard);
+
+        notified.await;
+        mutex.lock().await
+    }
+
+    /// Waits for notifications until `condition` returns `false`.
+    ///
+    /// The condition is checked with the mutex locked before waiting for the first time and
+    /// after every notification.
+    pub async fn wait_while<'a, T>(
+        &self,
+        mut guard: MutexGuard<'a, T>,
+        mut condition: impl FnMut(&mut T) -> bool,
+    ) -> MutexGuard<'a, T> {
+        while condition(&mut guard) {
+            guard = self.wait(guard).await;
+        }
+        guard
+    }
+
+    /// Wakes the task that has been waiting the longest, if any.
+    pub fn notify_one(&self) {
+        self.notify.notify_waiter();
+    }
+
+    /// Wakes every waiting task.
+    pub fn notify_all(&self) {
+        self.notify.notify_all();
+    }
+}
@@ -0,0 +1,29 @@
+//! Synchronization types for async tasks.
+//!
+//! Types implemented here are specifically designed to mimic the standard library.
+//!
+//! Every primitive in this module queues the wakers of tasks that are waiting on it and wakes
+//! them once they can make progress, so waiting tasks never spin. Waiters are served in the
+//! order they started waiting.
+//!
+//! Tasks can also communicate by passing values through the channels in [`mpsc`], [`oneshot`],
+//! [`broadcast`] and [`watch`].
+
+mod barrier;
+pub mod broadcast;
+mod condvar;
+pub mod mpsc;
+mod mutex;
+mod notify;
+pub mod oneshot;
+mod rwlock;
+mod semaphore;
+mod wait_queue;
+pub mod watch;
+
+pub use barrier::{Barrier, BarrierWaitResult};
+pub use condvar::Condvar;
+pub use mutex::{Mutex, MutexGuard, MutexLockFuture, RawMutex};
+pub use notify::{Notified, Notify};
+pub use rwlock::{RwLock, RwLockReadFuture, RwLockReadGuard, RwLockWriteFuture, RwLockWriteGuard};
+pub use semaphore::{Semaphore, SemaphoreAcquireFuture, SemaphorePermit};
@@ -0,0 +1,399 @@
+//! Multi-producer, single-consumer FIFO queues for sending values between tasks.
+//!
+//! A channel is created with either [`channel`], which holds at most a fixed number of
+//! messages and makes senders wait for space, or [`unbounded_channel`], which never makes
+//! senders wait.
+//!
+//! The channel is closed once the [`Receiver`] is dropped or every sender has been dropped.
+
+use alloc::{collections::VecDeque, sync::Arc};
+use core::{cell::RefCell, fmt};
+
+use snafu::Snafu;
+
+use super::notify::Notify;
+
+struct State<T> {
+    buffer: VecDeque<T>,
+    capacity: Option<usize>,
+    senders: usize,
+    receiver_alive: bool,
+}
+
+struct Shared<T> {
+    state: critical_section::Mutex<RefCell<State<T>>>,
+    /// Notified when a message is sent, the last sender is dropped or the receiver is closed.
+    recv_notify: Notify,
+    /// Notified when space is freed in the buffer or the receiver is dropped.
+    send_notify: Notify,
+}
+
+impl<T> Shared<T> {
+    fn new(capacity: Option<usize>) -> Arc<Self> {
+        Arc::new(Self {
+            state: critical_section::Mutex::new(RefCell::new(State {
+                buffer: VecDeque::new(),
+                capacity,
+                senders: 1,
+                receiver_alive: true,
+            })),
+            recv_notify: Notify::new(),
+            send_notify: Notify::new(),
+        })
+    }
+
+    fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+
+            if !state.receiver_alive {
+                return Err(TrySendError::Closed(value));
+            }
+            if state
+                .capacity
+                .is_some_and(|capacity| state.buffer.len() >= capacity)
+            {
+                return Err(TrySendError::Full(value));
+            }
+
+            state.buffer.push_back(value);
+            Ok(())
+        })?;
+
+        self.recv_notify.notify_one();
+        Ok(())
+    }
+
+    fn is_closed(&self) -> bool {
+        critical_section::with(|cs| !self.state.borrow_ref(cs).receiver_alive)
+    }
+
+    fn clone_sender(&self) {
+        critical_section::with(|cs| self.state.borrow_ref_mut(cs).senders += 1);
+    }
+
+    fn drop_sender(&self) {
+        let last = critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+            state.senders -= 1;
+            state.senders == 0
+        });
+
+        if last {
+            self.recv_notify.notify_one();
+        }
+    }
+}
+
+/// Creates a bounded channel that can hold up to `capacity` messages at once.
+///
+/// Once the channel is full, [`Sender::send`] waits until the receiver has made room.
+///
+/// # Panics
+///
+/// Panics if `capacity` is 0.
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "mpsc channel capacity must be greater than 0");
+
+    let shared = Shared::new(Some(capacity));
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared },
+    )
+}
+
+/// Creates a channel with no limit on the number of messages it can hold.
+pub fn unbounded_channel<T>() -> (UnboundedSender<T>, Receiver<T>) {
+    let shared = Shared::new(None);
+    (
+        UnboundedSender {
+            shared: shared.clone(),
+        },
+        Receiver { shared },
+    )
+}
+
+/// The sending half of a bounded channel created with [`channel`].
+///
+/// Senders can be cloned to send messages from several tasks.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a message, waiting for space in the channel if it is full.
+    ///
+    /// Returns the message back as an error if the receiver has been dropped.
+    pub async fn send(&self, mut value: T) -> Result<(), SendError<T>> {
+        loop {
+            let mut space_freed = self.shared.send_notify.notified();
+            space_freed.enable();
+
+            match self.shared.try_send(value) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Closed(value)) => return Err(SendError(value)),
+                Err(TrySendError::Full(returned)) => value = returned,
+            }
+
+            space_freed.await;
+        }
+    }
+
+    /// Attempts to send a message without waiting.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        self.shared.try_send(value)
+    }
+
+    /// Returns `true` if the receiver has been dropped or closed.
+    pub fn is_closed(&self) -> bool {
+        self.shared.is_closed()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.clone_sender();
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.drop_sender();
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// The sending half of an unbounded channel created with [`unbounded_channel`].
+///
+/// Senders can be cloned to send messages from several tasks.
+pub struct UnboundedSender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> UnboundedSender<T> {
+    /// Sends a message without waiting.
+    ///
+    /// Returns the message back as an error if the receiver has been dropped.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.shared.try_send(value).map_err(|error| match error {
+            TrySendError::Full(value) | TrySendError::Closed(value) => SendError(value),
+        })
+    }
+
+    /// Returns `true` if the receiver has been dropped or closed.
+    pub fn is_closed(&self) -> bool {
+        self.shared.is_closed()
+    }
+}
+
+impl<T> Clone for UnboundedSender<T> {
+    fn clone(&self) -> Self {
+        self.shared.clone_sender();
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for UnboundedSender<T> {
+    fn drop(&mut self) {
+        self.shared.drop_sender();
+    }
+}
+
+impl<T> fmt::Debug for UnboundedSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedSender").finish_non_exhaustive()
+    }
+}
+
+/// The receiving half of a channel.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Receives the next message, waiting until one is sent.
+    ///
+    /// Returns `None` once the channel is empty and either every sender has been dropped or
+    /// the channel has been [closed](Self::close).
+    pub async fn recv(&mut self) -> Option<T> {
+        let shared = self.shared.clone();
+        loop {
+            let mut sent = shared.recv_notify.notified();
+            sent.enable();
+
+            match self.try_recv() {
+                Ok(value) => return Some(value),
+                Err(TryRecvError::Disconnected) => return None,
+                Err(TryRecvError::Empty) => {}
+            }
+
+            sent.await;
+        }
+    }
+
+    /// Attempts to receive the next message without waiting.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let (value, bounded) = critical_section::with(|cs| {
+            let mut state = self.shared.state.borrow_ref_mut(cs);
+            match state.buffer.pop_front() {
+                Some(value) => Ok((value, state.capacity.is_some())),
+                None if state.senders == 0 || !state.receiver_alive => {
+                    Err(TryRecvError::Disconnected)
+                }
+                None => Err(TryRecvError::Empty),
+            }
+        })?;
+
+        if bounded {
+            self.shared.send_notify.notify_one();
+        }
+        Ok(value)
+    }
+
+    /// Closes the channel without dropping the receiver.
+    ///
+    /// Senders can no longer send messages, but messages already in the channel can still be
+    /// received. Once they have been, [`recv`](Self::recv) returns `None`.
+    pub fn close(&mut self) {
+        critical_section::with(|cs| self.shared.state.borrow_ref_mut(cs).receiver_alive = false);
+        self.shared.send_notify.notify_all();
+        self.shared.recv_notify.notify_all();
+    }
+
+    /// Returns the number of messages waiting in the channel.
+    pub fn len(&self) -> usize {
+        critical_section::with(|cs| self.shared.state.borrow_ref(cs).buffer.len())
+    }
+
+    /// Returns `true` if there are no messages waiting in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+/// Returned by [`Sender::send`] and [`UnboundedSender::send`] when the receiver has been
+/// dropped. Contains the message that could not be sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the channel is closed")
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for SendError<T> {}
+
+/// Errors returned by [`Sender::try_send`]. Contains the message that could not be sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// The channel is full.
+    Full(T),
+    /// The receiver has been dropped.
+    Closed(T),
+}
+
+impl<T> TrySendError<T> {
+    /// Returns the message that could not be sent.
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Full(value) | Self::Closed(value) => value,
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full(_) => f.write_str("the channel is full"),
+            Self::Closed(_) => f.write_str("the channel is closed"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for TrySendError<T> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Snafu)]
+/// Errors returned by [`Receiver::try_recv`].
+pub enum TryRecvError {
+    /// The channel is empty.
+    Empty,
+    /// The channel is empty and either every sender has been dropped or the channel has been
+    /// closed.
+    Disconnected,
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        future::Future,
+        pin::pin,
+        ptr,
+        task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    };
+
+    use super::*;
+
+    fn noop_waker() -> Waker {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(
+            |_| RawWaker::new(ptr::null(), &VTABLE),
+            |_| {},
+            |_| {},
+            |_| {},
+        );
+        unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+    }
+
+    fn poll_once<F: Future>(future: F) -> Poll<F::Output> {
+        let waker = noop_waker();
+        pin!(future).poll(&mut Context::from_waker(&waker))
+    }
+
+    #[test]
+    fn close_disconnects_once_drained() {
+        let (sender, mut receiver) = unbounded_channel();
+        sender.send(1).unwrap();
+
+        receiver.close();
+        assert!(sender.is_closed());
+        assert!(sender.send(2).is_err());
+
+        assert_eq!(receiver.try_recv(), Ok(1));
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Disconnected));
+        assert_eq!(poll_once(receiver.recv()), Poll::Ready(None));
+    }
+
+    #[test]
+    fn recv_waits_while_senders_are_alive() {
+        let (sender, mut receiver) = channel::<i32>(1);
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+        assert_eq!(poll_once(receiver.recv()), Poll::Pending);
+
+        drop(sender);
+        assert_eq!(poll_once(receiver.recv()), Poll::Ready(None));
+    }
+}
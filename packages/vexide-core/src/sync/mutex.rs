@@ -1,15 +1,15 @@
-//! Synchronization types for FreeRTOS tasks.
-//!
-//! Types implemented here are specifically designed to mimic the standard library.
+//! Mutual exclusion locks.
 
 use core::{
     cell::UnsafeCell,
     fmt::Debug,
     future::Future,
+    pin::Pin,
     sync::atomic::{AtomicU8, Ordering},
+    task::{Context, Poll},
 };
 
-use lock_api::RawMutex as _;
+use super::semaphore::{Semaphore, SemaphoreAcquireFuture};
 
 struct MutexState(AtomicU8);
 impl MutexState {
@@ -30,6 +30,9 @@ impl MutexState {
 }
 
 /// A raw mutex type built on top of the critical section.
+///
+/// Unlike [`Mutex`], this spins while the lock is contended and never allocates, which makes it
+/// suitable for use inside of the global allocator.
 pub struct RawMutex {
     state: MutexState,
 }
@@ -68,28 +71,37 @@ unsafe impl lock_api::RawMutex for RawMutex {
 }
 
 /// A future that resolves to a mutex guard.
+///
+/// Dropping this future gives up its place in the mutex's queue.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct MutexLockFuture<'a, T> {
     mutex: &'a Mutex<T>,
+    acquire: SemaphoreAcquireFuture<'a>,
 }
 impl<'a, T> Future for MutexLockFuture<'a, T> {
     type Output = MutexGuard<'a, T>;
 
-    fn poll(
-        self: core::pin::Pin<&mut Self>,
-        _: &mut core::task::Context<'_>,
-    ) -> core::task::Poll<Self::Output> {
-        if self.mutex.raw.try_lock() {
-            core::task::Poll::Ready(MutexGuard::new(self.mutex))
-        } else {
-            core::task::Poll::Pending
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.acquire).poll(cx) {
+            Poll::Ready(permit) => {
+                // The guard releases the lock itself when dropped.
+                permit.forget();
+                Poll::Ready(MutexGuard::new(self.mutex))
+            }
+            Poll::Pending => Poll::Pending,
         }
     }
 }
 
 /// The basic mutex type.
 /// Mutexes are used to share variables between tasks safely.
+///
+/// Tasks waiting on [`Mutex::lock`] are queued and acquire the lock in the order they started
+/// waiting. Each waiting task is woken when the lock is handed to it, so no task spins while
+/// the mutex is contended.
 pub struct Mutex<T> {
-    raw: RawMutex,
+    lock: Semaphore,
     data: UnsafeCell<T>,
 }
 unsafe impl<T: Send> Send for Mutex<T> {}
@@ -99,7 +111,7 @@ impl<T> Mutex<T> {
     /// Creates a new mutex.
     pub const fn new(data: T) -> Self {
         Self {
-            raw: RawMutex::new(),
+            lock: Semaphore::new(1),
             data: UnsafeCell::new(data),
         }
     }
@@ -107,23 +119,30 @@ impl<T> Mutex<T> {
     /// Locks the mutex so that it cannot be locked in another task at the same time.
     /// Blocks the current task until the lock is acquired.
     pub const fn lock(&self) -> MutexLockFuture<'_, T> {
-        MutexLockFuture { mutex: self }
+        MutexLockFuture {
+            mutex: self,
+            acquire: self.lock.acquire(),
+        }
     }
 
     /// Used internally to lock the mutex in a blocking fashion.
     /// This is neccessary because a mutex may be created internally before the executor is ready to be initialized.
     pub(crate) fn lock_blocking(&self) -> MutexGuard<'_, T> {
-        self.raw.lock();
-        MutexGuard::new(self)
+        loop {
+            if let Some(guard) = self.try_lock() {
+                return guard;
+            }
+            core::hint::spin_loop();
+        }
     }
 
     /// Attempts to acquire this lock. This function does not block.
+    ///
+    /// This fails if any task is already waiting to acquire the lock.
     pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
-        if self.raw.try_lock() {
-            Some(MutexGuard { mutex: self })
-        } else {
-            None
-        }
+        let permit = self.lock.try_acquire()?;
+        permit.forget();
+        Some(MutexGuard::new(self))
     }
 
     /// Consumes the mutex and returns the inner data.
@@ -175,6 +194,8 @@ impl<T> From<T> for Mutex<T> {
 
 /// Allows the user to access the data from a locked mutex.
 /// Dereference to get the inner data.
+///
+/// The lock is handed to the next waiting task when this is dropped.
 #[derive(Debug)]
 pub struct MutexGuard<'a, T> {
     mutex: &'a Mutex<T>,
@@ -184,6 +205,11 @@ impl<'a, T> MutexGuard<'a, T> {
     const fn new(mutex: &'a Mutex<T>) -> Self {
         Self { mutex }
     }
+
+    /// Returns the mutex that this guard has locked.
+    pub(crate) const fn mutex(&self) -> &'a Mutex<T> {
+        self.mutex
+    }
 }
 
 impl<T> core::ops::Deref for MutexGuard<'_, T> {
@@ -201,6 +227,6 @@ impl<T> core::ops::DerefMut for MutexGuard<'_, T> {
 
 impl<T> Drop for MutexGuard<'_, T> {
     fn drop(&mut self) {
-        unsafe { self.mutex.raw.unlock() };
+        self.mutex.lock.add_permits(1);
     }
 }
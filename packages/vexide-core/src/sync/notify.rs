@@ -0,0 +1,209 @@
+//! Task notification.
+
+use core::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::wait_queue::WaitQueue;
+
+/// How a waiting [`Notified`] future was notified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Notification {
+    One,
+    All,
+}
+
+struct NotifyState {
+    permit: bool,
+    waiters: WaitQueue<Option<Notification>>,
+}
+
+impl NotifyState {
+    /// Notifies the longest-waiting task that has not been notified yet.
+    ///
+    /// Returns `false` if there was no such task.
+    fn notify_waiter(&mut self) -> bool {
+        match self.waiters.iter_mut().find(|waiter| waiter.data.is_none()) {
+            Some(waiter) => {
+                waiter.data = Some(Notification::One);
+                waiter.wake();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Notifies a single task, or every waiting task, that an event has happened.
+///
+/// A task waits for a notification by awaiting [`Notify::notified`]. If [`Notify::notify_one`]
+/// is called while no task is waiting, the notification is stored and the next call to
+/// `notified` completes immediately.
+pub struct Notify {
+    state: critical_section::Mutex<RefCell<NotifyState>>,
+}
+
+impl Notify {
+    /// Creates a new `Notify` with no stored notification.
+    pub const fn new() -> Self {
+        Self {
+            state: critical_section::Mutex::new(RefCell::new(NotifyState {
+                permit: false,
+                waiters: WaitQueue::new(),
+            })),
+        }
+    }
+
+    /// Waits for a notification.
+    ///
+    /// The returned future is only queued for [`Notify::notify_all`] once it has been polled or
+    /// [enabled](Notified::enable).
+    pub const fn notified(&self) -> Notified<'_> {
+        Notified {
+            notify: self,
+            state: NotifiedState::Idle,
+        }
+    }
+
+    /// Wakes the task that has been waiting the longest.
+    ///
+    /// If no task is waiting, the notification is stored until the next call to
+    /// [`Notify::notified`]. At most one notification is stored.
+    pub fn notify_one(&self) {
+        critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+            if !state.notify_waiter() {
+                state.permit = true;
+            }
+        });
+    }
+
+    /// Wakes every waiting task.
+    ///
+    /// Unlike [`Notify::notify_one`], nothing is stored if no task is waiting.
+    pub fn notify_all(&self) {
+        critical_section::with(|cs| {
+            for waiter in self.state.borrow_ref_mut(cs).waiters.iter_mut() {
+                if waiter.data.is_none() {
+                    waiter.data = Some(Notification::All);
+                    waiter.wake();
+                }
+            }
+        });
+    }
+
+    /// Wakes the task that has been waiting the longest without storing a notification if
+    /// there is none.
+    pub(crate) fn notify_waiter(&self) {
+        critical_section::with(|cs| {
+            self.state.borrow_ref_mut(cs).notify_waiter();
+        });
+    }
+}
+
+impl Default for Notify {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for Notify {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Notify").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NotifiedState {
+    Idle,
+    Waiting(u64),
+    Notified,
+}
+
+/// A future that resolves once a [`Notify`] has been notified.
+///
+/// If this future is dropped after being woken by [`Notify::notify_one`] but before it was
+/// polled, the notification is passed on to the next waiting task.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Notified<'a> {
+    notify: &'a Notify,
+    state: NotifiedState,
+}
+
+impl Notified<'_> {
+    /// Queues this future to receive notifications without waiting for one.
+    ///
+    /// This is used to avoid missing a notification that is sent between checking a condition
+    /// and awaiting this future.
+    pub fn enable(&mut self) {
+        self.register(None);
+    }
+
+    fn register(&mut self, waker: Option<&core::task::Waker>) {
+        let notify = self.notify;
+
+        critical_section::with(|cs| {
+            let mut state = notify.state.borrow_ref_mut(cs);
+
+            match self.state {
+                NotifiedState::Idle => {
+                    if state.permit {
+                        state.permit = false;
+                        self.state = NotifiedState::Notified;
+                    } else {
+                        let id = state.waiters.push(None, waker.cloned());
+                        self.state = NotifiedState::Waiting(id);
+                    }
+                }
+                NotifiedState::Waiting(id) => {
+                    let notified = state
+                        .waiters
+                        .get_mut(id)
+                        .is_some_and(|waiter| waiter.data.is_some());
+
+                    if notified {
+                        state.waiters.remove(id);
+                        self.state = NotifiedState::Notified;
+                    } else if let Some(waker) = waker {
+                        state.waiters.register(id, waker);
+                    }
+                }
+                NotifiedState::Notified => {}
+            }
+        });
+    }
+}
+
+impl Future for Notified<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.register(Some(cx.waker()));
+
+        if self.state == NotifiedState::Notified {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for Notified<'_> {
+    fn drop(&mut self) {
+        if let NotifiedState::Waiting(id) = self.state {
+            critical_section::with(|cs| {
+                let mut state = self.notify.state.borrow_ref_mut(cs);
+
+                if let Some(waiter) = state.waiters.remove(id) {
+                    if waiter.data == Some(Notification::One) && !state.notify_waiter() {
+                        state.permit = true;
+                    }
+                }
+            });
+        }
+    }
+}
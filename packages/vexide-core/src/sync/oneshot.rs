@@ -0,0 +1,168 @@
+//! Channels for sending a single value between tasks.
+//!
+//! A oneshot channel is created with [`channel`]. The [`Receiver`] can be awaited directly to
+//! wait for the value.
+
+use alloc::sync::Arc;
+use core::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use snafu::Snafu;
+
+struct State<T> {
+    value: Option<T>,
+    sender_alive: bool,
+    receiver_alive: bool,
+    receiver_waker: Option<Waker>,
+}
+
+type Shared<T> = critical_section::Mutex<RefCell<State<T>>>;
+
+/// Creates a new oneshot channel.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(critical_section::Mutex::new(RefCell::new(State {
+        value: None,
+        sender_alive: true,
+        receiver_alive: true,
+        receiver_waker: None,
+    })));
+
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared },
+    )
+}
+
+/// The sending half of a oneshot channel.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends the value to the receiver.
+    ///
+    /// Returns the value back as an error if the receiver has been dropped or closed.
+    pub fn send(self, value: T) -> Result<(), T> {
+        critical_section::with(|cs| {
+            let mut state = self.shared.borrow_ref_mut(cs);
+            if !state.receiver_alive {
+                return Err(value);
+            }
+
+            state.value = Some(value);
+            if let Some(waker) = state.receiver_waker.take() {
+                waker.wake();
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns `true` if the receiver has been dropped or closed.
+    pub fn is_closed(&self) -> bool {
+        critical_section::with(|cs| !self.shared.borrow_ref(cs).receiver_alive)
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        critical_section::with(|cs| {
+            let mut state = self.shared.borrow_ref_mut(cs);
+            state.sender_alive = false;
+            if let Some(waker) = state.receiver_waker.take() {
+                waker.wake();
+            }
+        });
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// The receiving half of a oneshot channel.
+///
+/// Awaiting the receiver resolves to the sent value, or an error if the sender was dropped
+/// without sending one.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Waits for the value to be sent.
+    ///
+    /// This is equivalent to awaiting the receiver directly.
+    pub async fn recv(self) -> Result<T, RecvError> {
+        self.await
+    }
+
+    /// Attempts to receive the value without waiting.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        critical_section::with(|cs| {
+            let mut state = self.shared.borrow_ref_mut(cs);
+            match state.value.take() {
+                Some(value) => Ok(value),
+                None if !state.sender_alive => Err(TryRecvError::Closed),
+                None => Err(TryRecvError::Empty),
+            }
+        })
+    }
+
+    /// Prevents the sender from sending a value.
+    ///
+    /// A value that was already sent can still be received.
+    pub fn close(&mut self) {
+        critical_section::with(|cs| self.shared.borrow_ref_mut(cs).receiver_alive = false);
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        critical_section::with(|cs| {
+            let mut state = self.shared.borrow_ref_mut(cs);
+            match state.value.take() {
+                Some(value) => Poll::Ready(Ok(value)),
+                None if !state.sender_alive => Poll::Ready(Err(RecvError)),
+                None => {
+                    state.receiver_waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        })
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Snafu)]
+/// The sender was dropped without sending a value.
+pub struct RecvError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Snafu)]
+/// Errors returned by [`Receiver::try_recv`].
+pub enum TryRecvError {
+    /// No value has been sent yet.
+    Empty,
+    /// The sender was dropped without sending a value.
+    Closed,
+}
@@ -0,0 +1,202 @@
+//! Reader-writer locks.
+
+use core::{
+    cell::UnsafeCell,
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::semaphore::{Semaphore, SemaphoreAcquireFuture};
+
+/// A reader-writer lock.
+///
+/// Any number of tasks may hold read access at the same time, while write access is exclusive.
+/// Tasks acquire the lock in the order they started waiting, so a waiting writer is not starved
+/// by readers that arrive after it.
+pub struct RwLock<T> {
+    lock: Semaphore,
+    data: UnsafeCell<T>,
+}
+unsafe impl<T: Send> Send for RwLock<T> {}
+unsafe impl<T: Send + Sync> Sync for RwLock<T> {}
+
+impl<T> RwLock<T> {
+    /// The maximum number of tasks that can hold read access at once.
+    ///
+    /// A writer acquires every permit of the lock's internal semaphore.
+    pub const MAX_READERS: usize = usize::MAX >> 3;
+
+    /// Creates a new reader-writer lock.
+    pub const fn new(data: T) -> Self {
+        Self {
+            lock: Semaphore::new(Self::MAX_READERS),
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Locks the lock with shared read access, waiting until no writer holds it.
+    pub const fn read(&self) -> RwLockReadFuture<'_, T> {
+        RwLockReadFuture {
+            rwlock: self,
+            acquire: self.lock.acquire(),
+        }
+    }
+
+    /// Locks the lock with exclusive write access, waiting until no other task holds it.
+    pub const fn write(&self) -> RwLockWriteFuture<'_, T> {
+        RwLockWriteFuture {
+            rwlock: self,
+            acquire: self.lock.acquire_many(Self::MAX_READERS),
+        }
+    }
+
+    /// Attempts to acquire shared read access without waiting.
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        self.lock.try_acquire()?.forget();
+        Some(RwLockReadGuard { rwlock: self })
+    }
+
+    /// Attempts to acquire exclusive write access without waiting.
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        self.lock.try_acquire_many(Self::MAX_READERS)?.forget();
+        Some(RwLockWriteGuard { rwlock: self })
+    }
+
+    /// Consumes the lock and returns the inner data.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+
+    /// Gets a mutable reference to the inner data.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+}
+
+impl<T> Debug for RwLock<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        struct Placeholder;
+        impl Debug for Placeholder {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("<locked>")
+            }
+        }
+
+        let mut d = f.debug_struct("RwLock");
+        match self.try_read() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &Placeholder),
+        };
+        d.finish_non_exhaustive()
+    }
+}
+
+impl<T> Default for RwLock<T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for RwLock<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A future that resolves to a [`RwLockReadGuard`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RwLockReadFuture<'a, T> {
+    rwlock: &'a RwLock<T>,
+    acquire: SemaphoreAcquireFuture<'a>,
+}
+impl<'a, T> Future for RwLockReadFuture<'a, T> {
+    type Output = RwLockReadGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.acquire).poll(cx) {
+            Poll::Ready(permit) => {
+                permit.forget();
+                Poll::Ready(RwLockReadGuard {
+                    rwlock: self.rwlock,
+                })
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A future that resolves to a [`RwLockWriteGuard`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RwLockWriteFuture<'a, T> {
+    rwlock: &'a RwLock<T>,
+    acquire: SemaphoreAcquireFuture<'a>,
+}
+impl<'a, T> Future for RwLockWriteFuture<'a, T> {
+    type Output = RwLockWriteGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.acquire).poll(cx) {
+            Poll::Ready(permit) => {
+                permit.forget();
+                Poll::Ready(RwLockWriteGuard {
+                    rwlock: self.rwlock,
+                })
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Shared read access to the data in a [`RwLock`].
+#[derive(Debug)]
+pub struct RwLockReadGuard<'a, T> {
+    rwlock: &'a RwLock<T>,
+}
+
+impl<T> core::ops::Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.rwlock.data.get() }
+    }
+}
+
+impl<T> Drop for RwLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.rwlock.lock.add_permits(1);
+    }
+}
+
+/// Exclusive write access to the data in a [`RwLock`].
+#[derive(Debug)]
+pub struct RwLockWriteGuard<'a, T> {
+    rwlock: &'a RwLock<T>,
+}
+
+impl<T> core::ops::Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.rwlock.data.get() }
+    }
+}
+
+impl<T> core::ops::DerefMut for RwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.rwlock.data.get() }
+    }
+}
+
+impl<T> Drop for RwLockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.rwlock.lock.add_permits(RwLock::<T>::MAX_READERS);
+    }
+}
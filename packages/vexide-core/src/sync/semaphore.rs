@@ -0,0 +1,245 @@
+//! Asynchronous counting semaphore.
+
+use core::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::wait_queue::WaitQueue;
+
+struct AcquireRequest {
+    permits: usize,
+    granted: bool,
+}
+
+struct SemaphoreState {
+    permits: usize,
+    waiters: WaitQueue<AcquireRequest>,
+}
+
+impl SemaphoreState {
+    /// Hands out permits to waiting tasks in the order they started waiting.
+    ///
+    /// Stops at the first waiter that cannot be satisfied so that large requests are not
+    /// starved by smaller ones.
+    fn grant(&mut self) {
+        for waiter in self.waiters.iter_mut() {
+            if waiter.data.granted {
+                continue;
+            }
+            if waiter.data.permits > self.permits {
+                break;
+            }
+
+            self.permits -= waiter.data.permits;
+            waiter.data.granted = true;
+            waiter.wake();
+        }
+    }
+
+    fn has_pending_waiters(&mut self) -> bool {
+        self.waiters.iter_mut().any(|waiter| !waiter.data.granted)
+    }
+}
+
+/// A counting semaphore for limiting access to a shared resource.
+///
+/// Tasks acquire permits with [`Semaphore::acquire`] and give them back by dropping the
+/// returned [`SemaphorePermit`]. Permits are handed out in the order they were requested.
+pub struct Semaphore {
+    state: critical_section::Mutex<RefCell<SemaphoreState>>,
+}
+
+impl Semaphore {
+    /// Creates a new semaphore with the given number of permits.
+    pub const fn new(permits: usize) -> Self {
+        Self {
+            state: critical_section::Mutex::new(RefCell::new(SemaphoreState {
+                permits,
+                waiters: WaitQueue::new(),
+            })),
+        }
+    }
+
+    /// Returns the number of permits that can currently be acquired.
+    pub fn available_permits(&self) -> usize {
+        critical_section::with(|cs| self.state.borrow_ref(cs).permits)
+    }
+
+    /// Adds permits to the semaphore, waking any tasks that can now acquire them.
+    pub fn add_permits(&self, permits: usize) {
+        critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+            state.permits += permits;
+            state.grant();
+        });
+    }
+
+    /// Acquires a single permit, waiting until one is available.
+    pub const fn acquire(&self) -> SemaphoreAcquireFuture<'_> {
+        self.acquire_many(1)
+    }
+
+    /// Acquires several permits at once, waiting until they are all available.
+    pub const fn acquire_many(&self, permits: usize) -> SemaphoreAcquireFuture<'_> {
+        SemaphoreAcquireFuture {
+            semaphore: self,
+            permits,
+            state: AcquireState::Idle,
+        }
+    }
+
+    /// Attempts to acquire a single permit without waiting.
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.try_acquire_many(1)
+    }
+
+    /// Attempts to acquire several permits without waiting.
+    ///
+    /// This fails if any other task is already waiting for permits, even if enough permits are
+    /// available.
+    pub fn try_acquire_many(&self, permits: usize) -> Option<SemaphorePermit<'_>> {
+        critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+            if state.permits >= permits && !state.has_pending_waiters() {
+                state.permits -= permits;
+                Some(SemaphorePermit {
+                    semaphore: self,
+                    permits,
+                })
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl core::fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("permits", &self.available_permits())
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AcquireState {
+    Idle,
+    Waiting(u64),
+    Done,
+}
+
+/// A future that resolves once permits have been acquired from a [`Semaphore`].
+///
+/// Dropping this future gives up its place in the queue.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SemaphoreAcquireFuture<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+    state: AcquireState,
+}
+
+impl<'a> Future for SemaphoreAcquireFuture<'a> {
+    type Output = SemaphorePermit<'a>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let semaphore = self.semaphore;
+        let permits = self.permits;
+
+        let ready = critical_section::with(|cs| {
+            let mut state = semaphore.state.borrow_ref_mut(cs);
+
+            match self.state {
+                AcquireState::Idle => {
+                    if state.permits >= permits && !state.has_pending_waiters() {
+                        state.permits -= permits;
+                        true
+                    } else {
+                        let id = state.waiters.push(
+                            AcquireRequest {
+                                permits,
+                                granted: false,
+                            },
+                            Some(cx.waker().clone()),
+                        );
+                        self.state = AcquireState::Waiting(id);
+                        false
+                    }
+                }
+                AcquireState::Waiting(id) => {
+                    let granted = state
+                        .waiters
+                        .get_mut(id)
+                        .is_some_and(|waiter| waiter.data.granted);
+
+                    if granted {
+                        state.waiters.remove(id);
+                    } else {
+                        state.waiters.register(id, cx.waker());
+                    }
+                    granted
+                }
+                AcquireState::Done => panic!("`SemaphoreAcquireFuture` polled after completion"),
+            }
+        });
+
+        if ready {
+            self.state = AcquireState::Done;
+            Poll::Ready(SemaphorePermit { semaphore, permits })
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for SemaphoreAcquireFuture<'_> {
+    fn drop(&mut self) {
+        if let AcquireState::Waiting(id) = self.state {
+            critical_section::with(|cs| {
+                let mut state = self.semaphore.state.borrow_ref_mut(cs);
+
+                // Give back any permits that were granted to us but never claimed, and let
+                // the waiters behind us try again now that we are out of the way.
+                if let Some(waiter) = state.waiters.remove(id) {
+                    if waiter.data.granted {
+                        state.permits += waiter.data.permits;
+                    }
+                }
+                state.grant();
+            });
+        }
+    }
+}
+
+/// Permits acquired from a [`Semaphore`].
+///
+/// The permits are returned to the semaphore when this is dropped.
+#[derive(Debug)]
+#[must_use = "permits are released immediately if unused"]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+}
+
+impl SemaphorePermit<'_> {
+    /// Returns the number of permits held.
+    pub const fn permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Consumes the permit without returning it to the semaphore.
+    pub fn forget(mut self) {
+        self.permits = 0;
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        if self.permits > 0 {
+            self.semaphore.add_permits(self.permits);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! FIFO queue of tasks waiting on a synchronization primitive.
+
+use alloc::collections::VecDeque;
+use core::task::Waker;
+
+/// A task waiting in a [`WaitQueue`].
+pub(crate) struct Waiter<T> {
+    pub id: u64,
+    pub waker: Option<Waker>,
+    pub data: T,
+}
+
+impl<T> Waiter<T> {
+    /// Wakes the waiting task, if it has registered a waker.
+    pub fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A first-in, first-out queue of waiting tasks.
+///
+/// Each waiter is identified by a unique id so that the future it belongs to can find it again
+/// when it is polled or dropped.
+pub(crate) struct WaitQueue<T> {
+    waiters: VecDeque<Waiter<T>>,
+    next_id: u64,
+}
+
+impl<T> WaitQueue<T> {
+    pub const fn new() -> Self {
+        Self {
+            waiters: VecDeque::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Adds a waiter to the back of the queue, returning its id.
+    pub fn push(&mut self, data: T, waker: Option<Waker>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.waiters.push_back(Waiter { id, waker, data });
+        id
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut Waiter<T>> {
+        self.waiters.iter_mut().find(|waiter| waiter.id == id)
+    }
+
+    pub fn remove(&mut self, id: u64) -> Option<Waiter<T>> {
+        let index = self.waiters.iter().position(|waiter| waiter.id == id)?;
+        self.waiters.remove(index)
+    }
+
+    /// Replaces the waker of the waiter with the given id.
+    pub fn register(&mut self, id: u64, waker: &Waker) {
+        if let Some(waiter) = self.get_mut(id) {
+            match &mut waiter.waker {
+                Some(existing) => existing.clone_from(waker),
+                None => waiter.waker = Some(waker.clone()),
+            }
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Waiter<T>> {
+        self.waiters.iter_mut()
+    }
+}
@@ -0,0 +1,271 @@
+//! Single-producer, multi-consumer channels that only keep the latest value.
+//!
+//! A watch channel is created with [`channel`] and always holds a value. Receivers can read the
+//! current value at any time, and wait for it to change with [`Receiver::changed`]. This is
+//! useful for publishing state such as sensor readings, where only the most recent value
+//! matters.
+
+use alloc::sync::Arc;
+use core::{cell::RefCell, fmt, mem};
+
+use snafu::Snafu;
+
+use super::notify::Notify;
+
+struct State<T> {
+    value: T,
+    /// Incremented every time the value changes.
+    version: u64,
+    sender_alive: bool,
+    receivers: usize,
+}
+
+struct Shared<T> {
+    state: critical_section::Mutex<RefCell<State<T>>>,
+    /// Notified when the value changes or the sender is dropped.
+    notify: Notify,
+}
+
+/// Creates a watch channel holding an initial value.
+///
+/// The initial value is considered to have already been seen by the receiver.
+pub fn channel<T>(initial: T) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        state: critical_section::Mutex::new(RefCell::new(State {
+            value: initial,
+            version: 0,
+            sender_alive: true,
+            receivers: 1,
+        })),
+        notify: Notify::new(),
+    });
+
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver {
+            shared,
+            seen_version: 0,
+        },
+    )
+}
+
+/// The sending half of a watch channel.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Replaces the value and notifies every receiver.
+    ///
+    /// Returns the value back as an error if there are no receivers.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        if self.receiver_count() == 0 {
+            return Err(SendError(value));
+        }
+
+        self.send_replace(value);
+        Ok(())
+    }
+
+    /// Replaces the value and notifies every receiver, returning the previous value.
+    ///
+    /// Unlike [`Sender::send`], the value is stored even if there are no receivers.
+    pub fn send_replace(&self, value: T) -> T {
+        self.send_modify(|current| mem::replace(current, value))
+    }
+
+    /// Modifies the value in place and notifies every receiver.
+    pub fn send_modify<R>(&self, modify: impl FnOnce(&mut T) -> R) -> R {
+        let result = critical_section::with(|cs| {
+            let mut state = self.shared.state.borrow_ref_mut(cs);
+            state.version += 1;
+            modify(&mut state.value)
+        });
+
+        self.shared.notify.notify_all();
+        result
+    }
+
+    /// Runs a closure with a reference to the current value.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        critical_section::with(|cs| f(&self.shared.state.borrow_ref(cs).value))
+    }
+
+    /// Creates a new receiver that has seen the current value.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let seen_version = critical_section::with(|cs| {
+            let mut state = self.shared.state.borrow_ref_mut(cs);
+            state.receivers += 1;
+            state.version
+        });
+
+        Receiver {
+            shared: self.shared.clone(),
+            seen_version,
+        }
+    }
+
+    /// Returns the number of receivers.
+    pub fn receiver_count(&self) -> usize {
+        critical_section::with(|cs| self.shared.state.borrow_ref(cs).receivers)
+    }
+
+    /// Returns `true` if every receiver has been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.receiver_count() == 0
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        critical_section::with(|cs| self.shared.state.borrow_ref_mut(cs).sender_alive = false);
+        self.shared.notify.notify_all();
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// The receiving half of a watch channel.
+///
+/// Cloning a receiver creates another receiver that has seen the same version of the value.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    seen_version: u64,
+}
+
+impl<T> Receiver<T> {
+    /// Waits until the value changes from the last one this receiver has seen, then marks the
+    /// new value as seen.
+    ///
+    /// Returns an error if the sender has been dropped.
+    pub async fn changed(&mut self) -> Result<(), RecvError> {
+        loop {
+            let mut changed = self.shared.notify.notified();
+            changed.enable();
+
+            let (version, sender_alive) = critical_section::with(|cs| {
+                let state = self.shared.state.borrow_ref(cs);
+                (state.version, state.sender_alive)
+            });
+
+            if version != self.seen_version {
+                self.seen_version = version;
+                return Ok(());
+            }
+            if !sender_alive {
+                return Err(RecvError);
+            }
+
+            changed.await;
+        }
+    }
+
+    /// Returns `true` if the value has changed since this receiver last saw it.
+    ///
+    /// Returns an error if the sender has been dropped.
+    pub fn has_changed(&self) -> Result<bool, RecvError> {
+        critical_section::with(|cs| {
+            let state = self.shared.state.borrow_ref(cs);
+            if state.sender_alive {
+                Ok(state.version != self.seen_version)
+            } else {
+                Err(RecvError)
+            }
+        })
+    }
+
+    /// Runs a closure with a reference to the current value without marking it as seen.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        critical_section::with(|cs| f(&self.shared.state.borrow_ref(cs).value))
+    }
+
+    /// Marks the current value as seen.
+    pub fn mark_seen(&mut self) {
+        self.seen_version = critical_section::with(|cs| self.shared.state.borrow_ref(cs).version);
+    }
+}
+
+impl<T: Clone> Receiver<T> {
+    /// Returns a copy of the current value without marking it as seen.
+    pub fn get(&self) -> T {
+        self.with(T::clone)
+    }
+
+    /// Waits for the value to change, then returns a copy of the new value.
+    ///
+    /// Returns an error if the sender has been dropped.
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        self.changed().await?;
+        Ok(self.get())
+    }
+
+    /// Returns a copy of the value if it has changed since this receiver last saw it, marking
+    /// it as seen.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        critical_section::with(|cs| {
+            let state = self.shared.state.borrow_ref(cs);
+            if state.version != self.seen_version {
+                self.seen_version = state.version;
+                Ok(state.value.clone())
+            } else if !state.sender_alive {
+                Err(TryRecvError::Closed)
+            } else {
+                Err(TryRecvError::Empty)
+            }
+        })
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        critical_section::with(|cs| self.shared.state.borrow_ref_mut(cs).receivers += 1);
+        Self {
+            shared: self.shared.clone(),
+            seen_version: self.seen_version,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        critical_section::with(|cs| self.shared.state.borrow_ref_mut(cs).receivers -= 1);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+/// Returned by [`Sender::send`] when there are no receivers. Contains the value that could not
+/// be sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the channel has no receivers")
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for SendError<T> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Snafu)]
+/// The sender has been dropped.
+pub struct RecvError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Snafu)]
+/// Errors returned by [`Receiver::try_recv`].
+pub enum TryRecvError {
+    /// The value has not changed since it was last seen.
+    Empty,
+    /// The sender has been dropped.
+    Closed,
+}
@@ -1,7 +1,23 @@
 //! Utilities for getting competition control state.
+//!
+//! Robots that take part in competitions can implement [`Compete`] and hand themselves to
+//! [`CompeteExt::compete`], which runs the right code for the current competition mode and
+//! switches between modes as field control changes them.
+//!
+//! Lower-level code can wait for the competition state to change with [`updates`],
+//! [`wait_for_mode`] and [`wait_until_enabled`]. These are woken by the async reactor when the
+//! status changes rather than checking it in a loop.
+
+use core::{
+    future::Future,
+    pin::{pin, Pin},
+    task::{Context, Poll},
+};
 
 use bitflags::bitflags;
+use futures_core::Stream;
 use vex_sdk::vexCompetitionStatus;
+use vexide_async::competition::{status_changed, StatusChangedFuture};
 
 bitflags! {
     /// The status bits returned by [`competition::state`].
@@ -21,6 +37,36 @@ bitflags! {
     }
 }
 
+impl CompetitionStatus {
+    /// Gets the competition mode, or phase, described by these flags.
+    pub const fn mode(&self) -> CompetitionMode {
+        if self.contains(Self::DISABLED) {
+            CompetitionMode::Disabled
+        } else if self.contains(Self::AUTONOMOUS) {
+            CompetitionMode::Autonomous
+        } else {
+            CompetitionMode::Driver
+        }
+    }
+
+    /// Checks if these flags describe a robot connected to a competition control system.
+    pub const fn is_connected(&self) -> bool {
+        self.contains(Self::CONNECTED)
+    }
+
+    /// Gets the type of system controlling the robot's competition state, or [`None`] if these
+    /// flags describe a robot that is not tethered to a competition controller.
+    pub const fn system(&self) -> Option<CompetitionSystem> {
+        if !self.is_connected() {
+            None
+        } else if self.contains(Self::SYSTEM) {
+            Some(CompetitionSystem::FieldControl)
+        } else {
+            Some(CompetitionSystem::CompetitionSwitch)
+        }
+    }
+}
+
 /// Represents a possible mode that robots can be set in during the competition lifecycle.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CompetitionMode {
@@ -71,34 +117,267 @@ pub fn status() -> CompetitionStatus {
 
 /// Gets the current competition mode, or phase.
 pub fn mode() -> CompetitionMode {
-    let status = status();
-
-    if status.contains(CompetitionStatus::DISABLED) {
-        CompetitionMode::Disabled
-    } else if status.contains(CompetitionStatus::AUTONOMOUS) {
-        CompetitionMode::Autonomous
-    } else {
-        CompetitionMode::Driver
-    }
+    status().mode()
 }
 
 /// Checks if the robot is connected to a competition control system.
 pub fn connected() -> bool {
-    status().contains(CompetitionStatus::CONNECTED)
+    status().is_connected()
 }
 
 /// Gets the type of system currently controlling the robot's competition state, or [`None`] if the robot
 /// is not tethered to a competition controller.
 pub fn system() -> Option<CompetitionSystem> {
-    let status = status();
+    status().system()
+}
 
-    if status.contains(CompetitionStatus::CONNECTED) {
-        if status.contains(CompetitionStatus::SYSTEM) {
-            Some(CompetitionSystem::FieldControl)
-        } else {
-            Some(CompetitionSystem::CompetitionSwitch)
+/// A change in the robot's competition status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompetitionUpdate {
+    /// The status before the change.
+    pub previous: CompetitionStatus,
+    /// The status after the change.
+    pub status: CompetitionStatus,
+    /// The competition mode after the change.
+    pub mode: CompetitionMode,
+    /// The system controlling the robot's competition state after the change, or [`None`] if
+    /// the robot is no longer tethered to a competition controller.
+    pub system: Option<CompetitionSystem>,
+}
+
+impl CompetitionUpdate {
+    const fn new(previous: CompetitionStatus, status: CompetitionStatus) -> Self {
+        Self {
+            previous,
+            status,
+            mode: status.mode(),
+            system: status.system(),
         }
-    } else {
-        None
+    }
+}
+
+/// A stream of changes to the robot's competition status.
+///
+/// Created by [`updates`]. Each call to [`CompetitionUpdates::next`] waits for the status to
+/// differ from the one reported by the previous update. This also implements [`Stream`],
+/// which never ends.
+#[derive(Debug)]
+pub struct CompetitionUpdates {
+    last: CompetitionStatus,
+    change: Option<StatusChangedFuture>,
+}
+
+impl CompetitionUpdates {
+    /// Waits for the competition status to change, returning the change.
+    ///
+    /// If the status changed since the last update was returned, this completes immediately.
+    pub async fn next(&mut self) -> CompetitionUpdate {
+        core::future::poll_fn(|cx| self.poll_update(cx)).await
+    }
+
+    /// The status reported by the most recent update, or the status when the stream was
+    /// created if there have been no updates yet.
+    pub const fn last(&self) -> CompetitionStatus {
+        self.last
+    }
+
+    fn poll_update(&mut self, cx: &mut Context<'_>) -> Poll<CompetitionUpdate> {
+        let last = self.last.bits();
+        let change = self.change.get_or_insert_with(|| status_changed(last));
+        let Poll::Ready(status) = Pin::new(change).poll(cx) else {
+            return Poll::Pending;
+        };
+        self.change = None;
+
+        let status = CompetitionStatus::from_bits_retain(status);
+        let previous = core::mem::replace(&mut self.last, status);
+        Poll::Ready(CompetitionUpdate::new(previous, status))
+    }
+}
+
+impl Stream for CompetitionUpdates {
+    type Item = CompetitionUpdate;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_update(cx).map(Some)
+    }
+}
+
+/// Creates a stream of changes to the robot's competition status, starting from the current
+/// status.
+pub fn updates() -> CompetitionUpdates {
+    CompetitionUpdates {
+        last: status(),
+        change: None,
+    }
+}
+
+/// Waits for the next change to the robot's competition status.
+pub async fn status_change() -> CompetitionUpdate {
+    updates().next().await
+}
+
+/// Waits until the robot is in the given competition mode, returning the status at that point.
+///
+/// This completes immediately if the robot is already in that mode.
+pub async fn wait_for_mode(mode: CompetitionMode) -> CompetitionStatus {
+    let mut updates = updates();
+    while updates.last().mode() != mode {
+        updates.next().await;
+    }
+    updates.last()
+}
+
+/// Waits until the robot is enabled in either autonomous or driver control, returning the
+/// status at that point.
+///
+/// This completes immediately if the robot is already enabled.
+pub async fn wait_until_enabled() -> CompetitionStatus {
+    let mut updates = updates();
+    while updates.last().mode() == CompetitionMode::Disabled {
+        updates.next().await;
+    }
+    updates.last()
+}
+
+/// The part of the competition state that decides which [`Compete`] routine should run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Phase {
+    mode: CompetitionMode,
+    connected: bool,
+}
+
+impl Phase {
+    const fn from_status(status: CompetitionStatus) -> Self {
+        Self {
+            mode: status.mode(),
+            connected: status.is_connected(),
+        }
+    }
+}
+
+/// A robot that can take part in a competition.
+///
+/// Each method is a routine that runs while the robot is in the matching part of the
+/// competition lifecycle. When the competition mode changes, the routine that is running is
+/// dropped, even if it has not finished, and the routine for the new mode is started. All
+/// routines do nothing by default.
+///
+/// Robots are run with [`CompeteExt::compete`].
+#[allow(async_fn_in_trait)]
+pub trait Compete {
+    /// Runs when the robot connects to a competition control system.
+    ///
+    /// This runs before the routine for the mode the robot was connected in.
+    async fn connected(&mut self) {}
+
+    /// Runs when the robot disconnects from a competition control system.
+    ///
+    /// This runs before the routine for the mode the robot is in after disconnecting.
+    async fn disconnected(&mut self) {}
+
+    /// Runs the first time the robot enters each competition mode.
+    ///
+    /// This runs before the routine for the mode being entered.
+    async fn first_entry(&mut self, mode: CompetitionMode) {
+        _ = mode;
+    }
+
+    /// Runs while the robot is disabled.
+    async fn disabled(&mut self) {}
+
+    /// Runs while the robot is in autonomous mode.
+    async fn autonomous(&mut self) {}
+
+    /// Runs while the robot is in driver control mode.
+    ///
+    /// This is also the mode the robot is in when it is not connected to a competition
+    /// control system.
+    async fn driver(&mut self) {}
+}
+
+/// Extension methods for [`Compete`].
+#[allow(async_fn_in_trait)]
+pub trait CompeteExt: Compete {
+    /// Runs the robot through the competition lifecycle.
+    ///
+    /// The routine for the current mode is started immediately, and is replaced by the routine
+    /// for the new mode whenever the competition mode changes. This never returns.
+    async fn compete(self);
+}
+
+impl<R: Compete> CompeteExt for R {
+    async fn compete(mut self) {
+        let mut entered = [false; 3];
+        let mut previous: Option<Phase> = None;
+
+        loop {
+            let status = status();
+            let phase = Phase::from_status(status);
+            let connection_changed = previous.map_or(phase.connected, |previous| {
+                previous.connected != phase.connected
+            });
+            let first_entry = !core::mem::replace(&mut entered[phase.mode as usize], true);
+
+            let robot = &mut self;
+            let routine = pin!(async move {
+                if connection_changed {
+                    if phase.connected {
+                        robot.connected().await;
+                    } else {
+                        robot.disconnected().await;
+                    }
+                }
+
+                if first_entry {
+                    robot.first_entry(phase.mode).await;
+                }
+
+                match phase.mode {
+                    CompetitionMode::Disabled => robot.disabled().await,
+                    CompetitionMode::Autonomous => robot.autonomous().await,
+                    CompetitionMode::Driver => robot.driver().await,
+                }
+            });
+
+            UntilPhaseChange {
+                routine: Some(routine),
+                phase,
+                change: status_changed(status.bits()),
+            }
+            .await;
+
+            previous = Some(phase);
+        }
+    }
+}
+
+/// Runs a routine until the competition phase changes, dropping it if it has not finished.
+struct UntilPhaseChange<F> {
+    routine: Option<F>,
+    phase: Phase,
+    change: StatusChangedFuture,
+}
+
+impl<F: Future<Output = ()> + Unpin> Future for UntilPhaseChange<F> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Changes that don't affect the phase, such as switching between field control and a
+        // competition switch, keep the current routine running.
+        while let Poll::Ready(status) = Pin::new(&mut self.change).poll(cx) {
+            if Phase::from_status(CompetitionStatus::from_bits_retain(status)) != self.phase {
+                return Poll::Ready(());
+            }
+            self.change = status_changed(status);
+        }
+
+        if let Some(routine) = &mut self.routine {
+            if Pin::new(routine).poll(cx).is_ready() {
+                self.routine = None;
+            }
+        }
+
+        Poll::Pending
     }
 }
@@ -18,6 +18,9 @@
 
 extern crate alloc;
 
+#[cfg(not(any(target_arch = "arm", target_arch = "wasm32")))]
+extern crate vexide_sim as vex_sdk;
+
 pub mod adi;
 pub mod smart;
 
@@ -2,7 +2,7 @@
 //!
 //! Positions have many conversion functions as well as common operator implementations for ease of use.
 
-use core::{cmp::Ordering, ops::*};
+use core::{cmp::Ordering, f64::consts::PI, ops::*};
 
 //TODO: Add more unit types to this.
 /// Represents an angular position.
@@ -12,7 +12,9 @@ pub enum Position {
     Degrees(f64),
     /// Counts of full rotations, 360 degrees.
     Rotations(f64),
-    /// Raw encoder ticks.
+    /// Raw encoder ticks, assuming 4096 ticks per rotation.
+    ///
+    /// Use [`Position::from_ticks`] for encoders with a different resolution.
     Counts(i64),
 }
 
@@ -46,7 +48,7 @@ impl Position {
         match self {
             Self::Degrees(num) => num / 360.0,
             Self::Rotations(num) => num,
-            Self::Counts(num) => num as f64 * 4096.0,
+            Self::Counts(num) => num as f64 / 4096.0,
         }
     }
 
@@ -58,8 +60,120 @@ impl Position {
             Self::Counts(num) => num,
         }
     }
+
+    /// Creates a position from raw encoder ticks on an encoder with the given number of ticks
+    /// per rotation.
+    pub fn from_ticks(ticks: i64, ticks_per_rotation: f64) -> Self {
+        Self::Rotations(ticks as f64 / ticks_per_rotation)
+    }
+
+    /// Converts a position into raw encoder ticks on an encoder with the given number of ticks
+    /// per rotation.
+    pub fn into_ticks(self, ticks_per_rotation: f64) -> i64 {
+        (self.into_rotations() * ticks_per_rotation) as i64
+    }
+}
+
+/// External gearing between a motor or sensor and the mechanism it measures.
+///
+/// Motors and sensors measure the rotation of their own shaft. When a mechanism is geared or
+/// chained to that shaft, a [`Gearing`] converts between the rotation of the device and the
+/// rotation of the mechanism's output shaft, and optionally the linear distance travelled by a
+/// wheel or sprocket on the output shaft.
+///
+/// Gearings are equal if their ratios and wheel diameters have exactly the same bits, so that
+/// devices holding a gearing can still be compared with [`Eq`].
+#[derive(Clone, Copy, Debug)]
+pub struct Gearing {
+    /// How many times the device turns for each turn of the output shaft.
+    ///
+    /// For example, a motor with a 12 tooth gear driving a 60 tooth gear has a ratio of 5.0.
+    pub ratio: f64,
+
+    /// The diameter of the wheel or sprocket on the output shaft in inches, if linear distances
+    /// are measured.
+    pub wheel_diameter: Option<f64>,
+}
+
+impl Gearing {
+    /// A device connected directly to the output shaft.
+    pub const DIRECT: Self = Self::new(1.0);
+
+    /// Creates gearing where the device turns `ratio` times for each turn of the output shaft.
+    ///
+    /// The gearing is checked when it is given to a device, which panics if `ratio` isn't a
+    /// positive, finite number.
+    pub const fn new(ratio: f64) -> Self {
+        Self {
+            ratio,
+            wheel_diameter: None,
+        }
+    }
+
+    /// Sets the diameter in inches of the wheel or sprocket on the output shaft.
+    ///
+    /// The gearing is checked when it is given to a device, which panics if `diameter` isn't a
+    /// positive, finite number.
+    pub const fn with_wheel_diameter(mut self, diameter: f64) -> Self {
+        self.wheel_diameter = Some(diameter);
+        self
+    }
+
+    /// Converts a position of the device into a position of the output shaft.
+    pub fn to_output(&self, position: Position) -> Position {
+        Position::from_degrees(position.into_degrees() / self.ratio)
+    }
+
+    /// Converts a position of the output shaft into a position of the device.
+    pub fn from_output(&self, position: Position) -> Position {
+        Position::from_degrees(position.into_degrees() * self.ratio)
+    }
+
+    /// Converts a position of the output shaft into the distance travelled by its wheel in
+    /// inches, returning `None` if there is no wheel diameter.
+    pub fn to_distance(&self, position: Position) -> Option<f64> {
+        self.wheel_diameter
+            .map(|diameter| position.into_rotations() * PI * diameter)
+    }
+
+    /// Converts a distance travelled by the wheel in inches into a position of the output
+    /// shaft, returning `None` if there is no wheel diameter.
+    pub fn from_distance(&self, distance: f64) -> Option<Position> {
+        self.wheel_diameter
+            .map(|diameter| Position::from_rotations(distance / (PI * diameter)))
+    }
+
+    /// Panics unless the ratio and wheel diameter are positive, finite numbers.
+    pub(crate) fn validate(&self) {
+        assert!(
+            self.ratio > 0.0 && self.ratio.is_finite(),
+            "gearing ratio must be positive and finite, got {}",
+            self.ratio
+        );
+        if let Some(diameter) = self.wheel_diameter {
+            assert!(
+                diameter > 0.0 && diameter.is_finite(),
+                "wheel diameter must be positive and finite, got {diameter}"
+            );
+        }
+    }
 }
 
+impl Default for Gearing {
+    fn default() -> Self {
+        Self::DIRECT
+    }
+}
+
+impl PartialEq for Gearing {
+    fn eq(&self, other: &Self) -> bool {
+        self.ratio.to_bits() == other.ratio.to_bits()
+            && self.wheel_diameter.map(f64::to_bits) == other.wheel_diameter.map(f64::to_bits)
+    }
+}
+
+impl Eq for Gearing {}
+
 impl Add for Position {
     type Output = Self;
 
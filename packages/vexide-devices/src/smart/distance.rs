@@ -73,7 +73,7 @@ impl DistanceSensor {
     pub fn distance_confidence(&self) -> Result<f64, DistanceError> {
         self.validate()?;
 
-        Ok(unsafe { vexDeviceDistanceConfidenceGet(self.device_handle()) as u32 } as f64 / 63.0)
+        Ok(unsafe { vexDeviceDistanceConfidenceGet(self.device_handle()) } as f64 / 63.0)
     }
 
     /// Gets the status code of the distance sensor
@@ -28,6 +28,7 @@ pub mod motor;
 pub mod optical;
 pub mod rotation;
 pub mod serial;
+pub mod supervisor;
 pub mod vision;
 
 use core::fmt;
@@ -1,32 +1,44 @@
 //! V5 Smart Motors
 
-use core::time::Duration;
+use alloc::vec::Vec;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use bitflags::bitflags;
 use snafu::Snafu;
 use vex_sdk::{
-    vexDeviceMotorAbsoluteTargetSet, vexDeviceMotorBrakeModeSet, vexDeviceMotorCurrentGet,
-    vexDeviceMotorCurrentLimitGet, vexDeviceMotorCurrentLimitSet, vexDeviceMotorEfficiencyGet,
-    vexDeviceMotorEncoderUnitsSet, vexDeviceMotorFaultsGet, vexDeviceMotorFlagsGet,
-    vexDeviceMotorGearingGet, vexDeviceMotorGearingSet, vexDeviceMotorPositionGet,
-    vexDeviceMotorPositionRawGet, vexDeviceMotorPositionReset, vexDeviceMotorPositionSet,
-    vexDeviceMotorPowerGet, vexDeviceMotorReverseFlagGet, vexDeviceMotorReverseFlagSet,
-    vexDeviceMotorTemperatureGet, vexDeviceMotorTorqueGet, vexDeviceMotorVelocityGet,
-    vexDeviceMotorVelocitySet, vexDeviceMotorVelocityUpdate, vexDeviceMotorVoltageGet,
-    vexDeviceMotorVoltageLimitGet, vexDeviceMotorVoltageLimitSet, vexDeviceMotorVoltageSet,
-    V5MotorBrakeMode, V5MotorGearset,
+    vexDeviceGetByIndex, vexDeviceMotorAbsoluteTargetSet, vexDeviceMotorBrakeModeSet,
+    vexDeviceMotorCurrentGet, vexDeviceMotorCurrentLimitGet, vexDeviceMotorCurrentLimitSet,
+    vexDeviceMotorEfficiencyGet, vexDeviceMotorEncoderUnitsSet, vexDeviceMotorFaultsGet,
+    vexDeviceMotorFlagsGet, vexDeviceMotorGearingGet, vexDeviceMotorGearingSet,
+    vexDeviceMotorPositionGet, vexDeviceMotorPositionRawGet, vexDeviceMotorPositionReset,
+    vexDeviceMotorPositionSet, vexDeviceMotorPowerGet, vexDeviceMotorReverseFlagGet,
+    vexDeviceMotorReverseFlagSet, vexDeviceMotorTemperatureGet, vexDeviceMotorTorqueGet,
+    vexDeviceMotorVelocityGet, vexDeviceMotorVelocitySet, vexDeviceMotorVelocityUpdate,
+    vexDeviceMotorVoltageGet, vexDeviceMotorVoltageLimitGet, vexDeviceMotorVoltageLimitSet,
+    vexDeviceMotorVoltageSet, V5MotorBrakeMode, V5MotorGearset,
 };
 #[cfg(feature = "dangerous_motor_tuning")]
 use vex_sdk::{vexDeviceMotorPositionPidSet, vexDeviceMotorVelocityPidSet, V5_DeviceMotorPid};
+use vexide_async::{sleep_until, SleepFuture};
+use vexide_core::time::Instant;
 
-use super::{SmartDevice, SmartDeviceInternal, SmartDeviceTimestamp, SmartDeviceType, SmartPort};
-use crate::{PortError, Position};
+use super::{
+    validate_port, SmartDevice, SmartDeviceInternal, SmartDeviceTimestamp, SmartDeviceType,
+    SmartPort,
+};
+use crate::{position::Gearing, PortError, Position};
 
 /// The basic motor struct.
 #[derive(Debug, PartialEq)]
 pub struct Motor {
     port: SmartPort,
     target: MotorControl,
+    gearing: Gearing,
 }
 
 /// Represents a possible target for a [`Motor`].
@@ -83,33 +95,68 @@ impl Motor {
     /// The rate at which data can be written to a [`Motor`].
     pub const DATA_WRITE_RATE: Duration = Duration::from_millis(5);
 
+    /// The velocity in RPM, measured at the motor itself, below which
+    /// [`Motor::wait_until_settled`] considers the motor stopped.
+    pub const SETTLED_VELOCITY: i32 = 5;
+
     /// Create a new motor from a smart port index.
     pub fn new(
         port: SmartPort,
         gearset: Gearset,
         direction: Direction,
     ) -> Result<Self, MotorError> {
-        let mut motor = Self {
+        let mut motor = Self::unconfigured(port);
+        motor.configure(gearset, direction)?;
+
+        Ok(motor)
+    }
+
+    /// Creates a motor without sending any configuration to it.
+    const fn unconfigured(port: SmartPort) -> Self {
+        Self {
             port,
             target: MotorControl::Voltage(0.0),
-        };
+            gearing: Gearing::DIRECT,
+        }
+    }
 
-        motor.set_gearset(gearset)?;
-        motor.set_direction(direction)?;
+    /// Sends the configuration that [`Motor::new`] sets up to the motor.
+    fn configure(&mut self, gearset: Gearset, direction: Direction) -> Result<(), MotorError> {
+        self.set_gearset(gearset)?;
+        self.set_direction(direction)?;
 
         unsafe {
             vexDeviceMotorEncoderUnitsSet(
-                motor.device_handle(),
+                self.device_handle(),
                 vex_sdk::V5MotorEncoderUnits::kMotorEncoderDegrees,
             );
         }
 
-        Ok(motor)
+        Ok(())
+    }
+
+    /// Sets the external [`Gearing`] between the motor and the mechanism it drives.
+    ///
+    /// Positions and velocities read from and sent to the motor are measured at the output shaft
+    /// of the gearing, and linear distances can be measured if it has a wheel diameter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gearing's ratio or wheel diameter isn't a positive, finite number.
+    pub fn set_gearing(&mut self, gearing: Gearing) {
+        gearing.validate();
+        self.gearing = gearing;
+    }
+
+    /// Gets the external [`Gearing`] between the motor and the mechanism it drives.
+    pub const fn gearing(&self) -> Gearing {
+        self.gearing
     }
 
     /// Sets the target that the motor should attempt to reach.
     ///
-    /// This could be a voltage, velocity, position, or even brake mode.
+    /// This could be a voltage, velocity, position, or even brake mode. Velocities and positions
+    /// are measured at the output shaft of the motor's [`Gearing`].
     pub fn set_target(&mut self, target: MotorControl) -> Result<(), MotorError> {
         self.validate_port()?;
         self.target = target;
@@ -125,7 +172,7 @@ impl Motor {
                     self.device_handle(),
                     vex_sdk::V5MotorBrakeMode::kV5MotorBrakeModeCoast,
                 );
-                vexDeviceMotorVelocitySet(self.device_handle(), rpm);
+                vexDeviceMotorVelocitySet(self.device_handle(), self.motor_rpm(rpm));
             },
             MotorControl::Voltage(volts) => unsafe {
                 vexDeviceMotorBrakeModeSet(
@@ -141,8 +188,8 @@ impl Motor {
                 );
                 vexDeviceMotorAbsoluteTargetSet(
                     self.device_handle(),
-                    position.into_degrees(),
-                    velocity,
+                    self.gearing.from_output(position).into_degrees(),
+                    self.motor_rpm(velocity),
                 );
             },
         }
@@ -181,6 +228,30 @@ impl Motor {
         self.set_target(MotorControl::Position(position, velocity))
     }
 
+    /// Spins the motor so that its wheel moves at a target linear velocity in inches per second.
+    ///
+    /// This requires the motor's [`Gearing`] to have a wheel diameter.
+    pub fn set_linear_velocity(&mut self, velocity: f64) -> Result<(), MotorLinearError> {
+        let rpm = self.output_rpm(velocity)?;
+        Ok(self.set_velocity(rpm)?)
+    }
+
+    /// Sets an absolute target for the distance travelled by the motor's wheel in inches,
+    /// moving at up to `velocity` RPM.
+    ///
+    /// This requires the motor's [`Gearing`] to have a wheel diameter.
+    pub fn set_distance_target(
+        &mut self,
+        distance: f64,
+        velocity: i32,
+    ) -> Result<(), MotorLinearError> {
+        let position = self
+            .gearing
+            .from_distance(distance)
+            .ok_or(MotorLinearError::NoWheelDiameter)?;
+        Ok(self.set_position_target(position, velocity)?)
+    }
+
     /// Changes the output velocity for a profiled movement (motor_move_absolute or motor_move_relative).
     ///
     /// This will have no effect if the motor is not following a profiled movement.
@@ -188,7 +259,7 @@ impl Motor {
         self.validate_port()?;
 
         unsafe {
-            vexDeviceMotorVelocityUpdate(self.device_handle(), velocity);
+            vexDeviceMotorVelocityUpdate(self.device_handle(), self.motor_rpm(velocity));
         }
 
         if let MotorControl::Position(position, _) = self.target {
@@ -204,6 +275,63 @@ impl Motor {
         Ok(self.target)
     }
 
+    /// Returns a future that completes once the motor has settled at its target.
+    ///
+    /// When following a position target set by [`Motor::set_position_target`], the motor is
+    /// settled once its position is within `tolerance` of the target and it has slowed to within
+    /// [`Motor::SETTLED_VELOCITY`] of stopping, so overshooting the target doesn't count. After
+    /// [`Motor::brake`], the motor is settled once it has stopped, and `tolerance` is unused.
+    ///
+    /// Voltage and velocity targets have no position to settle at, so the future fails with
+    /// [`MotorWaitError::UnsupportedTarget`] for them as soon as it's polled. Use [`Motor::wait_until_velocity`] to
+    /// wait for a motor to reach a velocity instead.
+    ///
+    /// The motor is checked each time it reports new data, roughly every
+    /// [`Motor::DATA_READ_RATE`]. The future fails with [`MotorWaitError::TimedOut`] if the
+    /// motor hasn't settled within `timeout`, or with a port error if the motor is disconnected
+    /// while waiting. Passing [`Duration::MAX`] waits without a timeout.
+    pub fn wait_until_settled(&self, tolerance: Position, timeout: Duration) -> MotorSettleFuture {
+        let condition = match self.target {
+            MotorControl::Position(target, _) => Some(SettleCondition::Position {
+                target: self.gearing.from_output(target).into_degrees(),
+                tolerance: self.gearing.from_output(tolerance).into_degrees(),
+            }),
+            MotorControl::Brake(_) => Some(SettleCondition::Velocity {
+                target: 0,
+                tolerance: Self::SETTLED_VELOCITY,
+            }),
+            MotorControl::Voltage(_) | MotorControl::Velocity(_) => None,
+        };
+
+        MotorSettleFuture::new(self.port.index(), condition, timeout)
+    }
+
+    /// Returns a future that completes once the motor's velocity is within `tolerance` RPM of
+    /// `rpm`.
+    ///
+    /// This works with any kind of target, so it can be used to wait for a flywheel driven by
+    /// [`Motor::set_voltage`] to get up to speed as well as after [`Motor::set_velocity`].
+    ///
+    /// The motor is checked each time it reports new data, roughly every
+    /// [`Motor::DATA_READ_RATE`]. The future fails with [`MotorWaitError::TimedOut`] if the
+    /// motor hasn't reached the velocity within `timeout`, or with a port error if the motor is
+    /// disconnected while waiting. Passing [`Duration::MAX`] waits without a timeout.
+    pub fn wait_until_velocity(
+        &self,
+        rpm: i32,
+        tolerance: i32,
+        timeout: Duration,
+    ) -> MotorSettleFuture {
+        MotorSettleFuture::new(
+            self.port.index(),
+            Some(SettleCondition::Velocity {
+                target: self.motor_rpm(rpm),
+                tolerance: self.motor_rpm(tolerance),
+            }),
+            timeout,
+        )
+    }
+
     /// Sets the gearset of the motor.
     pub fn set_gearset(&mut self, gearset: Gearset) -> Result<(), MotorError> {
         self.validate_port()?;
@@ -219,10 +347,20 @@ impl Motor {
         Ok(unsafe { vexDeviceMotorGearingGet(self.device_handle()) }.into())
     }
 
-    /// Gets the estimated angular velocity (RPM) of the motor.
-    pub fn velocity(&self) -> Result<i32, MotorError> {
+    /// Gets the estimated angular velocity (RPM) of the output shaft of the motor's [`Gearing`].
+    pub fn velocity(&self) -> Result<f64, MotorError> {
         self.validate_port()?;
-        Ok(unsafe { vexDeviceMotorVelocityGet(self.device_handle()) })
+        Ok(self.output_velocity(unsafe { vexDeviceMotorVelocityGet(self.device_handle()) }))
+    }
+
+    /// Gets the estimated linear velocity of the motor's wheel in inches per second.
+    ///
+    /// This requires the motor's [`Gearing`] to have a wheel diameter.
+    pub fn linear_velocity(&self) -> Result<f64, MotorLinearError> {
+        let rotations_per_second = Position::from_rotations(self.velocity()? / 60.0);
+        self.gearing
+            .to_distance(rotations_per_second)
+            .ok_or(MotorLinearError::NoWheelDiameter)
     }
 
     /// Returns the power drawn by the motor in Watts.
@@ -243,12 +381,22 @@ impl Motor {
         Ok(unsafe { vexDeviceMotorVoltageGet(self.device_handle()) } as f64 / 1000.0)
     }
 
-    /// Returns the current position of the motor.
+    /// Returns the current position of the output shaft of the motor's [`Gearing`].
     pub fn position(&self) -> Result<Position, MotorError> {
         self.validate_port()?;
-        Ok(Position::from_degrees(unsafe {
+        Ok(self.gearing.to_output(Position::from_degrees(unsafe {
             vexDeviceMotorPositionGet(self.device_handle())
-        }))
+        })))
+    }
+
+    /// Returns the total distance travelled by the motor's wheel in inches.
+    ///
+    /// This requires the motor's [`Gearing`] to have a wheel diameter.
+    pub fn distance(&self) -> Result<f64, MotorLinearError> {
+        let position = self.position()?;
+        self.gearing
+            .to_distance(position)
+            .ok_or(MotorLinearError::NoWheelDiameter)
     }
 
     /// Returns the most recently recorded raw encoder tick data from the motor's IME
@@ -292,7 +440,12 @@ impl Motor {
     /// Analogous to taring or resetting the encoder so that the new position is equal to the given position.
     pub fn set_position(&mut self, position: Position) -> Result<(), MotorError> {
         self.validate_port()?;
-        unsafe { vexDeviceMotorPositionSet(self.device_handle(), position.into_degrees()) }
+        unsafe {
+            vexDeviceMotorPositionSet(
+                self.device_handle(),
+                self.gearing.from_output(position).into_degrees(),
+            );
+        }
         Ok(())
     }
 
@@ -357,6 +510,48 @@ impl Motor {
         }))
     }
 
+    /// Reads every telemetry value from the motor at once.
+    ///
+    /// This is faster than calling each getter separately, since the port is only validated
+    /// once. The returned [`MotorTelemetry`] also records the timestamp of the data packet it was
+    /// read from, which can be passed to [`Motor::has_new_data_since`] to check whether the motor
+    /// has reported anything new.
+    pub fn telemetry(&self) -> Result<MotorTelemetry, MotorError> {
+        let status = self.status()?;
+        let handle = self.device_handle();
+
+        let mut timestamp: u32 = 0;
+        let raw_position = unsafe { vexDeviceMotorPositionRawGet(handle, &mut timestamp) };
+
+        unsafe {
+            Ok(MotorTelemetry {
+                timestamp: SmartDeviceTimestamp(timestamp),
+                position: self
+                    .gearing
+                    .to_output(Position::from_degrees(vexDeviceMotorPositionGet(handle))),
+                raw_position,
+                velocity: self.output_velocity(vexDeviceMotorVelocityGet(handle)),
+                power: vexDeviceMotorPowerGet(handle),
+                torque: vexDeviceMotorTorqueGet(handle),
+                voltage: vexDeviceMotorVoltageGet(handle) as f64 / 1000.0,
+                current: vexDeviceMotorCurrentGet(handle) as f64 / 1000.0,
+                efficiency: vexDeviceMotorEfficiencyGet(handle) / 100.0,
+                temperature: vexDeviceMotorTemperatureGet(handle),
+                faults: MotorFaults::from_bits_retain(vexDeviceMotorFaultsGet(handle)),
+                status,
+            })
+        }
+    }
+
+    /// Check if the motor has sent new data since the given timestamp, such as the
+    /// [`MotorTelemetry::timestamp`] of a previous reading.
+    ///
+    /// Motors send new data every [`Motor::DATA_READ_RATE`], so control loops running faster
+    /// than that can use this to skip iterations where nothing has changed.
+    pub fn has_new_data_since(&self, timestamp: SmartDeviceTimestamp) -> Result<bool, MotorError> {
+        Ok(self.raw_position()?.1 > timestamp)
+    }
+
     /// Check if the motor's over temperature flag is set.
     pub fn is_over_temperature(&self) -> Result<bool, MotorError> {
         Ok(self.faults()?.contains(MotorFaults::OVER_TEMPERATURE))
@@ -400,6 +595,26 @@ impl Motor {
         )
     }
 
+    /// Converts an RPM of the output shaft into the nearest whole RPM of the motor.
+    fn motor_rpm(&self, rpm: i32) -> i32 {
+        round_rpm(rpm as f64 * self.gearing.ratio)
+    }
+
+    /// Converts an RPM of the motor into an RPM of the output shaft.
+    fn output_velocity(&self, rpm: i32) -> f64 {
+        rpm as f64 / self.gearing.ratio
+    }
+
+    /// Converts a linear velocity of the wheel in inches per second into the nearest whole RPM
+    /// of the output shaft.
+    fn output_rpm(&self, velocity: f64) -> Result<i32, MotorLinearError> {
+        let rotations_per_second = self
+            .gearing
+            .from_distance(velocity)
+            .ok_or(MotorLinearError::NoWheelDiameter)?;
+        Ok(round_rpm(rotations_per_second.into_rotations() * 60.0))
+    }
+
     /// Adjusts the internal tuning constants of the motor when using velocity control.
     ///
     /// # Hardware Safety
@@ -447,6 +662,12 @@ impl Motor {
     }
 }
 
+/// Rounds an RPM to the nearest whole number, with halves rounded away from zero.
+fn round_rpm(rpm: f64) -> i32 {
+    // `f64::round` isn't available without `std`, and casting truncates towards zero.
+    (if rpm < 0.0 { rpm - 0.5 } else { rpm + 0.5 }) as i32
+}
+
 impl SmartDevice for Motor {
     fn port_index(&self) -> u8 {
         self.port.index()
@@ -457,6 +678,394 @@ impl SmartDevice for Motor {
     }
 }
 
+/// Several motors that are driven together as if they were a single motor.
+///
+/// Mechanisms such as drivetrain sides and lifts are often powered by more than one motor, all of
+/// which must receive the same commands. Every command sent to a motor group is forwarded to each
+/// of its members, and readings from the group combine the readings of each member.
+///
+/// A member that is disconnected doesn't stop the rest of the group from working. Commands are
+/// still sent to the members that are connected, readings only include connected members, and
+/// [`MotorGroup::disconnected`] reports which members are missing. Methods only fail when none of
+/// the members can be reached.
+///
+/// The group remembers the gearset, directions, limits and target it was given. Each time a
+/// command is sent, members that have been reconnected since the last command are configured
+/// with these settings again before the command is forwarded to them. A motor's encoder starts
+/// over when it loses power, so a reconnected member's position is also set to the average
+/// position of the rest of the group, or to the position the group was last set to if no other
+/// members are connected.
+///
+/// Readings include a reconnected member as soon as it is connected, so until the next command
+/// is sent its position may not match the rest of the group.
+#[derive(Debug, PartialEq)]
+pub struct MotorGroup {
+    members: Vec<GroupMember>,
+    gearset: Gearset,
+    current_limit: Option<f64>,
+    voltage_limit: Option<f64>,
+    target: MotorControl,
+    /// The position the group was last set to, if it has been.
+    position: Option<Position>,
+}
+
+/// A motor in a [`MotorGroup`], along with the settings that are applied when it connects.
+#[derive(Debug, PartialEq)]
+struct GroupMember {
+    motor: Motor,
+    direction: Direction,
+    /// Whether the group's settings have been sent to the motor since it was last connected.
+    configured: bool,
+}
+
+impl MotorGroup {
+    /// Create a new motor group from smart ports, each paired with the [`Direction`] of the motor
+    /// plugged into it.
+    ///
+    /// Members that are disconnected when the group is created are configured once they are
+    /// connected.
+    pub fn new(ports: impl IntoIterator<Item = (SmartPort, Direction)>, gearset: Gearset) -> Self {
+        let mut group = Self {
+            members: ports
+                .into_iter()
+                .map(|(port, direction)| GroupMember {
+                    motor: Motor::unconfigured(port),
+                    direction,
+                    configured: false,
+                })
+                .collect(),
+            gearset,
+            current_limit: None,
+            voltage_limit: None,
+            target: MotorControl::Voltage(0.0),
+            position: None,
+        };
+        group.configure_reconnected();
+        group
+    }
+
+    /// Get the motors in this group.
+    pub fn motors(&self) -> impl Iterator<Item = &Motor> + '_ {
+        self.members.iter().map(|member| &member.motor)
+    }
+
+    /// Consumes the group, returning its motors.
+    pub fn into_motors(self) -> Vec<Motor> {
+        self.members
+            .into_iter()
+            .map(|member| member.motor)
+            .collect()
+    }
+
+    /// Returns the port indices of members that are not currently connected.
+    pub fn disconnected(&self) -> impl Iterator<Item = u8> + '_ {
+        self.motors()
+            .filter(|motor| !motor.is_connected())
+            .map(SmartDevice::port_index)
+    }
+
+    /// Returns `true` if every member of the group is connected.
+    pub fn is_connected(&self) -> bool {
+        self.motors().all(SmartDevice::is_connected)
+    }
+
+    /// Sets the target that every motor in the group should attempt to reach.
+    ///
+    /// This could be a voltage, velocity, position, or even brake mode.
+    pub fn set_target(&mut self, target: MotorControl) -> Result<(), MotorGroupError> {
+        self.target = target;
+        self.for_each(|motor| motor.set_target(target))
+    }
+
+    /// Sets the target of every motor in the group to a given [`BrakeMode`].
+    pub fn brake(&mut self, mode: BrakeMode) -> Result<(), MotorGroupError> {
+        self.set_target(MotorControl::Brake(mode))
+    }
+
+    /// Spins every motor in the group at a target velocity.
+    ///
+    /// See [`Motor::set_velocity`] for more details.
+    pub fn set_velocity(&mut self, rpm: i32) -> Result<(), MotorGroupError> {
+        self.set_target(MotorControl::Velocity(rpm))
+    }
+
+    /// Sets the output voltage of every motor in the group.
+    ///
+    /// This voltage value spans from -12 (fully spinning reverse) to +12 (fully spinning forwards) volts, and
+    /// controls the raw output of the motors.
+    pub fn set_voltage(&mut self, volts: f64) -> Result<(), MotorGroupError> {
+        self.set_target(MotorControl::Voltage(volts))
+    }
+
+    /// Sets an absolute position target for every motor in the group to attempt to reach.
+    pub fn set_position_target(
+        &mut self,
+        position: Position,
+        velocity: i32,
+    ) -> Result<(), MotorGroupError> {
+        self.set_target(MotorControl::Position(position, velocity))
+    }
+
+    /// Get the last [`MotorControl`] value sent to the group.
+    pub const fn target(&self) -> MotorControl {
+        self.target
+    }
+
+    /// Sets the gearset of every motor in the group.
+    pub fn set_gearset(&mut self, gearset: Gearset) -> Result<(), MotorGroupError> {
+        self.gearset = gearset;
+        self.for_each(|motor| motor.set_gearset(gearset))
+    }
+
+    /// Sets the [`Direction`] of the member at `index`, in the order the group was created with.
+    ///
+    /// If the member is disconnected, the direction is set once it reconnects.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_direction(&mut self, index: usize, direction: Direction) {
+        let member = &mut self.members[index];
+        member.direction = direction;
+        if member.motor.set_direction(direction).is_err() {
+            member.configured = false;
+        }
+    }
+
+    /// Sets the external [`Gearing`] between every motor in the group and the mechanism they
+    /// drive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gearing's ratio or wheel diameter isn't a positive, finite number.
+    pub fn set_gearing(&mut self, gearing: Gearing) {
+        gearing.validate();
+        for member in &mut self.members {
+            member.motor.set_gearing(gearing);
+        }
+    }
+
+    /// Sets the current limit of each motor in the group in amps.
+    ///
+    /// The limit applies to every motor individually, so the group as a whole can draw this much
+    /// current per member.
+    pub fn set_current_limit(&mut self, limit: f64) -> Result<(), MotorGroupError> {
+        self.current_limit = Some(limit);
+        self.for_each(|motor| motor.set_current_limit(limit))
+    }
+
+    /// Sets the voltage limit of each motor in the group in volts.
+    pub fn set_voltage_limit(&mut self, limit: f64) -> Result<(), MotorGroupError> {
+        self.voltage_limit = Some(limit);
+        self.for_each(|motor| motor.set_voltage_limit(limit))
+    }
+
+    /// Sets the current encoder position of every motor in the group to zero without moving them.
+    pub fn reset_position(&mut self) -> Result<(), MotorGroupError> {
+        self.position = Some(Position::from_degrees(0.0));
+        self.for_each(Motor::reset_position)
+    }
+
+    /// Sets the current encoder position of every motor in the group to the given position
+    /// without moving them.
+    pub fn set_position(&mut self, position: Position) -> Result<(), MotorGroupError> {
+        self.position = Some(position);
+        self.for_each(|motor| motor.set_position(position))
+    }
+
+    /// Returns the average position of the connected motors in the group.
+    pub fn position(&self) -> Result<Position, MotorGroupError> {
+        average(self.readings(Motor::position).map(Position::into_degrees))
+            .map(Position::from_degrees)
+    }
+
+    /// Returns the average estimated angular velocity (RPM) of the connected motors in the group.
+    pub fn velocity(&self) -> Result<f64, MotorGroupError> {
+        average(self.readings(Motor::velocity))
+    }
+
+    /// Returns the total electrical current drawn by the connected motors in the group in amps.
+    pub fn current(&self) -> Result<f64, MotorGroupError> {
+        self.readings(Motor::current)
+            .reduce(|a, b| a + b)
+            .ok_or(MotorGroupError::NoMotorsConnected)
+    }
+
+    /// Returns the temperature of the hottest connected motor in the group.
+    pub fn temperature(&self) -> Result<f64, MotorGroupError> {
+        self.readings(Motor::temperature)
+            .reduce(f64::max)
+            .ok_or(MotorGroupError::NoMotorsConnected)
+    }
+
+    /// Returns the fault flags of every connected motor in the group combined.
+    ///
+    /// A flag is set if it is set on any of the motors.
+    pub fn faults(&self) -> Result<MotorFaults, MotorGroupError> {
+        self.readings(Motor::faults)
+            .reduce(|a, b| a | b)
+            .ok_or(MotorGroupError::NoMotorsConnected)
+    }
+
+    /// Check if any of the motors in the group are over temperature.
+    pub fn is_over_temperature(&self) -> Result<bool, MotorGroupError> {
+        Ok(self.faults()?.contains(MotorFaults::OVER_TEMPERATURE))
+    }
+
+    /// Check if any of the motors in the group are over current.
+    pub fn is_over_current(&self) -> Result<bool, MotorGroupError> {
+        Ok(self.faults()?.contains(MotorFaults::OVER_CURRENT))
+    }
+
+    /// Sends the group's settings to every member that has connected since it was last
+    /// configured, and marks disconnected members to be configured again.
+    fn configure_reconnected(&mut self) {
+        for member in &mut self.members {
+            if !member.motor.is_connected() {
+                member.configured = false;
+            }
+        }
+
+        if self.members.iter().all(|member| member.configured) {
+            return;
+        }
+
+        // Reconnected members are synced to the members that kept their positions.
+        let position = average(
+            self.members
+                .iter()
+                .filter(|member| member.configured)
+                .filter_map(|member| member.motor.position().ok())
+                .map(Position::into_degrees),
+        )
+        .map(Position::from_degrees)
+        .ok()
+        .or(self.position);
+
+        for member in &mut self.members {
+            if member.configured {
+                continue;
+            }
+            member.configured = member
+                .configure(
+                    self.gearset,
+                    self.current_limit,
+                    self.voltage_limit,
+                    self.target,
+                    position,
+                )
+                .is_ok();
+        }
+    }
+
+    /// Runs `f` on every configured motor in the group, failing only if it didn't succeed on
+    /// any of them.
+    fn for_each(
+        &mut self,
+        mut f: impl FnMut(&mut Motor) -> Result<(), MotorError>,
+    ) -> Result<(), MotorGroupError> {
+        self.configure_reconnected();
+
+        let mut succeeded = false;
+        for member in self.members.iter_mut().filter(|member| member.configured) {
+            match f(&mut member.motor) {
+                Ok(()) => succeeded = true,
+                Err(MotorError::Port { .. }) => member.configured = false,
+                Err(_) => {}
+            }
+        }
+
+        if succeeded {
+            Ok(())
+        } else {
+            Err(MotorGroupError::NoMotorsConnected)
+        }
+    }
+
+    /// Returns the readings from every motor in the group that could be read.
+    fn readings<'a, T>(
+        &'a self,
+        f: impl Fn(&Motor) -> Result<T, MotorError> + 'a,
+    ) -> impl Iterator<Item = T> + 'a {
+        self.motors().filter_map(move |motor| f(motor).ok())
+    }
+}
+
+impl GroupMember {
+    /// Sends the settings of the group to the motor.
+    fn configure(
+        &mut self,
+        gearset: Gearset,
+        current_limit: Option<f64>,
+        voltage_limit: Option<f64>,
+        target: MotorControl,
+        position: Option<Position>,
+    ) -> Result<(), MotorError> {
+        self.motor.configure(gearset, self.direction)?;
+        if let Some(limit) = current_limit {
+            self.motor.set_current_limit(limit)?;
+        }
+        if let Some(limit) = voltage_limit {
+            self.motor.set_voltage_limit(limit)?;
+        }
+        if let Some(position) = position {
+            self.motor.set_position(position)?;
+        }
+        self.motor.set_target(target)
+    }
+}
+
+/// Returns the mean of `values`, failing if there are none.
+fn average(values: impl Iterator<Item = f64>) -> Result<f64, MotorGroupError> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+
+    if count == 0 {
+        return Err(MotorGroupError::NoMotorsConnected);
+    }
+
+    Ok(sum / count as f64)
+}
+
+/// Every telemetry value reported by a [`Motor`], read from the same data packet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotorTelemetry {
+    /// When the motor recorded this data, according to its internal clock.
+    pub timestamp: SmartDeviceTimestamp,
+
+    /// The position of the output shaft of the motor's [`Gearing`].
+    pub position: Position,
+
+    /// The raw encoder tick count of the motor's IME.
+    pub raw_position: i32,
+
+    /// The estimated angular velocity of the output shaft of the motor's [`Gearing`] in RPM.
+    pub velocity: f64,
+
+    /// The power drawn by the motor in Watts.
+    pub power: f64,
+
+    /// The torque output of the motor in Nm.
+    pub torque: f64,
+
+    /// The voltage the motor is drawing in volts.
+    pub voltage: f64,
+
+    /// The electrical current draw of the motor in amps.
+    pub current: f64,
+
+    /// The efficiency of the motor from a range of [0.0, 1.0].
+    pub efficiency: f64,
+
+    /// The internal temperature of the motor in increments of 5°C.
+    pub temperature: f64,
+
+    /// The fault flags of the motor.
+    pub faults: MotorFaults,
+
+    /// The status flags of the motor.
+    pub status: MotorStatus,
+}
+
 /// Determines how a motor should act when braking.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum BrakeMode {
@@ -672,6 +1281,116 @@ impl From<MotorTuningConstants> for V5_DeviceMotorPid {
     }
 }
 
+/// What a [`MotorSettleFuture`] is waiting for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SettleCondition {
+    /// Waiting for the position in degrees to be within `tolerance` of `target`, and the motor to
+    /// have stopped.
+    Position { target: f64, tolerance: f64 },
+
+    /// Waiting for the velocity in RPM to be within `tolerance` of `target`.
+    Velocity { target: i32, tolerance: i32 },
+}
+
+/// Future that waits for a motor to reach its target, created with
+/// [`Motor::wait_until_settled`] or [`Motor::wait_until_velocity`].
+#[derive(Debug)]
+pub struct MotorSettleFuture {
+    port: u8,
+    /// What the motor has to reach, or `None` if its target has nothing to settle at.
+    condition: Option<SettleCondition>,
+    /// When the future times out, or `None` if the timeout is too long to ever be reached.
+    deadline: Option<Instant>,
+    last_timestamp: Option<u32>,
+    sleep: SleepFuture,
+}
+
+impl MotorSettleFuture {
+    fn new(port: u8, condition: Option<SettleCondition>, timeout: Duration) -> Self {
+        let now = Instant::now();
+
+        Self {
+            port,
+            condition,
+            deadline: now.checked_add(timeout),
+            last_timestamp: None,
+            sleep: sleep_until(now),
+        }
+    }
+
+    /// Checks whether the motor has reached its target, returning `None` if it hasn't sent any
+    /// new data since the last check.
+    fn check(&mut self, condition: SettleCondition) -> Result<Option<bool>, MotorWaitError> {
+        validate_port(self.port, SmartDeviceType::Motor).map_err(MotorError::from)?;
+        let handle = unsafe { vexDeviceGetByIndex((self.port - 1) as u32) };
+
+        // The flags can't be read while the motor is failing to communicate, so its other
+        // readings can't be trusted either.
+        let status = MotorStatus::from_bits_retain(unsafe { vexDeviceMotorFlagsGet(handle) });
+        if status.contains(MotorStatus::BUSY) {
+            return Ok(None);
+        }
+
+        // Readings are only updated when the motor sends a new packet, so the same packet is
+        // never checked twice.
+        let mut timestamp = 0;
+        unsafe {
+            vexDeviceMotorPositionRawGet(handle, &mut timestamp);
+        }
+        if self.last_timestamp.replace(timestamp) == Some(timestamp) {
+            return Ok(None);
+        }
+
+        Ok(Some(match condition {
+            SettleCondition::Position { target, tolerance } => {
+                let position = unsafe { vexDeviceMotorPositionGet(handle) };
+                let velocity = unsafe { vexDeviceMotorVelocityGet(handle) };
+                (target - tolerance..=target + tolerance).contains(&position)
+                    && velocity.unsigned_abs() <= Motor::SETTLED_VELOCITY.unsigned_abs()
+            }
+            SettleCondition::Velocity { target, tolerance } => {
+                let velocity = unsafe { vexDeviceMotorVelocityGet(handle) };
+                velocity.abs_diff(target) <= tolerance.unsigned_abs()
+            }
+        }))
+    }
+}
+
+impl Future for MotorSettleFuture {
+    type Output = Result<(), MotorWaitError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Some(condition) = self.condition else {
+            return Poll::Ready(Err(MotorWaitError::UnsupportedTarget));
+        };
+
+        loop {
+            if Pin::new(&mut self.sleep).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+
+            match self.check(condition) {
+                Ok(Some(true)) => return Poll::Ready(Ok(())),
+                Ok(_) => {}
+                // The motor got unplugged, so we'll resolve early.
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+
+            let now = Instant::now();
+            if self.deadline.is_some_and(|deadline| now >= deadline) {
+                return Poll::Ready(Err(MotorWaitError::TimedOut));
+            }
+
+            // Check again once the motor has had time to send new data.
+            let next_check = now + Motor::DATA_READ_RATE;
+            self.sleep = sleep_until(match self.deadline {
+                Some(deadline) => next_check.min(deadline),
+                None => next_check,
+            });
+        }
+    }
+}
+
 #[derive(Debug, Snafu)]
 /// Errors that can occur when using a motor.
 pub enum MotorError {
@@ -685,3 +1404,41 @@ pub enum MotorError {
         source: PortError,
     },
 }
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when measuring or moving a motor's wheel in linear units.
+pub enum MotorLinearError {
+    /// Linear distances can't be measured because the motor's [`Gearing`] has no wheel diameter.
+    NoWheelDiameter,
+
+    /// Failed to communicate with the motor.
+    #[snafu(display("{source}"), context(false))]
+    Motor {
+        /// The source of the error.
+        source: MotorError,
+    },
+}
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when waiting for a motor to reach its target.
+pub enum MotorWaitError {
+    /// The motor didn't reach its target before the timeout.
+    TimedOut,
+
+    /// The motor's target is a voltage or velocity, which has no position to settle at.
+    UnsupportedTarget,
+
+    /// Failed to read from the motor.
+    #[snafu(display("{source}"), context(false))]
+    Motor {
+        /// The source of the error.
+        source: MotorError,
+    },
+}
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when using a motor group.
+pub enum MotorGroupError {
+    /// None of the motors in the group could be reached.
+    NoMotorsConnected,
+}
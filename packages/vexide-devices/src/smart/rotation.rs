@@ -1,7 +1,10 @@
 //! Rotation sensor device.
 //!
 //! Rotation sensors operate on the same [`Position`] type as motors to measure rotation.
+//! Like motors, they can be given external [`Gearing`] to measure the rotation of a geared
+//! output shaft, or the distance travelled by a wheel.
 
+use snafu::Snafu;
 use vex_sdk::{
     vexDeviceAbsEncAngleGet, vexDeviceAbsEncPositionGet, vexDeviceAbsEncPositionSet,
     vexDeviceAbsEncReset, vexDeviceAbsEncReverseFlagGet, vexDeviceAbsEncReverseFlagSet,
@@ -9,19 +12,26 @@ use vex_sdk::{
 };
 
 use super::{motor::Direction, SmartDevice, SmartDeviceInternal, SmartDeviceType, SmartPort};
-use crate::{position::Position, PortError};
+use crate::{
+    position::{Gearing, Position},
+    PortError,
+};
 
 /// A physical rotation sensor plugged into a port.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct RotationSensor {
     port: SmartPort,
+    gearing: Gearing,
 }
 
 impl RotationSensor {
     /// Creates a new rotation sensor on the given port.
     /// Whether or not the sensor should be reversed on creation can be specified.
     pub fn new(port: SmartPort, direction: Direction) -> Result<Self, PortError> {
-        let mut sensor = Self { port };
+        let mut sensor = Self {
+            port,
+            gearing: Gearing::DIRECT,
+        };
 
         sensor.reset()?;
         sensor.set_direction(direction)?;
@@ -40,11 +50,42 @@ impl RotationSensor {
         Ok(())
     }
 
-    /// Sets the position.
+    /// Sets the external [`Gearing`] between the sensor and the mechanism it measures.
+    ///
+    /// Positions and velocities are measured at the output shaft of the gearing, and linear
+    /// distances can be measured if it has a wheel diameter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gearing's ratio or wheel diameter isn't a positive, finite number.
+    pub fn set_gearing(&mut self, gearing: Gearing) {
+        gearing.validate();
+        self.gearing = gearing;
+    }
+
+    /// Gets the external [`Gearing`] between the sensor and the mechanism it measures.
+    pub const fn gearing(&self) -> Gearing {
+        self.gearing
+    }
+
+    /// Sets the position of the output shaft.
     pub fn set_position(&mut self, position: Position) -> Result<(), PortError> {
         self.validate_port()?;
 
-        unsafe { vexDeviceAbsEncPositionSet(self.device_handle(), position.into_degrees() as i32) }
+        // The sensor stores its position in centidegrees, the same unit `position` reads.
+        let centidegrees = self.gearing.from_output(position).into_degrees() * 100.0;
+
+        unsafe {
+            vexDeviceAbsEncPositionSet(
+                self.device_handle(),
+                // Round to the nearest centidegree, since casting truncates towards zero.
+                (if centidegrees < 0.0 {
+                    centidegrees - 0.5
+                } else {
+                    centidegrees + 0.5
+                }) as i32,
+            )
+        }
 
         Ok(())
     }
@@ -70,18 +111,29 @@ impl RotationSensor {
         )
     }
 
-    /// Get the total number of degrees rotated by the sensor based on direction.
+    /// Get the total rotation of the output shaft based on direction.
     pub fn position(&self) -> Result<Position, PortError> {
         self.validate_port()?;
 
-        Ok(Position::from_degrees(
+        Ok(self.gearing.to_output(Position::from_degrees(
             unsafe { vexDeviceAbsEncPositionGet(self.device_handle()) } as f64 / 100.0,
-        ))
+        )))
+    }
+
+    /// Get the total distance travelled by the wheel on the output shaft in inches.
+    ///
+    /// This requires the sensor's [`Gearing`] to have a wheel diameter.
+    pub fn distance(&self) -> Result<f64, RotationError> {
+        let position = self.position()?;
+        self.gearing
+            .to_distance(position)
+            .ok_or(RotationError::NoWheelDiameter)
     }
 
     /// Get the angle of rotation measured by the sensor.
     ///
-    /// This value is reported from 0-360 degrees.
+    /// This value is reported from 0-360 degrees. Unlike [`RotationSensor::position`], this is
+    /// the angle of the sensor itself rather than the output shaft of its [`Gearing`].
     pub fn angle(&self) -> Result<Position, PortError> {
         self.validate_port()?;
 
@@ -90,11 +142,24 @@ impl RotationSensor {
         ))
     }
 
-    /// Get the sensor's current velocity in degrees per second
+    /// Get the output shaft's current velocity in degrees per second
     pub fn velocity(&self) -> Result<f64, PortError> {
         self.validate_port()?;
 
-        Ok(unsafe { vexDeviceAbsEncVelocityGet(self.device_handle()) as f64 / 1000.0 })
+        Ok(
+            unsafe { vexDeviceAbsEncVelocityGet(self.device_handle()) as f64 / 1000.0 }
+                / self.gearing.ratio,
+        )
+    }
+
+    /// Get the current linear velocity of the wheel on the output shaft in inches per second.
+    ///
+    /// This requires the sensor's [`Gearing`] to have a wheel diameter.
+    pub fn linear_velocity(&self) -> Result<f64, RotationError> {
+        let velocity = Position::from_degrees(self.velocity()?);
+        self.gearing
+            .to_distance(velocity)
+            .ok_or(RotationError::NoWheelDiameter)
     }
 
     /// Returns the sensor's status code.
@@ -114,3 +179,17 @@ impl SmartDevice for RotationSensor {
         SmartDeviceType::Rotation
     }
 }
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when measuring linear distances with a rotation sensor.
+pub enum RotationError {
+    /// Linear distances can't be measured because the sensor's [`Gearing`] has no wheel diameter.
+    NoWheelDiameter,
+
+    /// Generic port related error.
+    #[snafu(display("{source}"), context(false))]
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+}
@@ -0,0 +1,424 @@
+//! Motor stall, jam and thermal protection.
+//!
+//! A [`MotorSupervisor`] is an opt-in background task that watches a [`Motor`]'s current,
+//! velocity and temperature, and steps in before a mechanism destroys itself:
+//!
+//! - A motor that draws a lot of current while barely moving is stalled, such as an intake
+//!   jammed on a game piece or a lift pushing against a hard stop. Stalls are reported as a
+//!   [`SupervisorEvent::Stalled`], and with [`JamReversal`] the motor is briefly driven in reverse
+//!   to clear the jam before its previous target is restored.
+//! - With [`ThermalDerating`], the motor's current limit is lowered as it heats up, so that it
+//!   keeps working at reduced strength instead of shutting down when it overheats.
+//!
+//! The supervisor shares the motor with the rest of the program through a [`Mutex`]:
+//!
+//! ```no_run
+//! # extern crate alloc;
+//! # async fn supervise(motor: vexide_devices::smart::Motor) {
+//! use alloc::sync::Arc;
+//! use core::time::Duration;
+//!
+//! use vexide_core::sync::Mutex;
+//! use vexide_devices::smart::{
+//!     motor::BrakeMode,
+//!     supervisor::{JamReversal, MotorSupervisor, SupervisorEvent},
+//! };
+//!
+//! let intake = Arc::new(Mutex::new(motor));
+//! let mut events = MotorSupervisor::new()
+//!     .with_jam_reversal(JamReversal::new(6.0, Duration::from_millis(250)))
+//!     .spawn(&intake);
+//!
+//! intake.lock().await.set_voltage(12.0).unwrap();
+//!
+//! // Give up on the intake if reversing it doesn't clear the jam after a few tries. Braking
+//! // changes the target, so the supervisor won't restore the voltage after the last reversal.
+//! let mut jams = 0;
+//! while let Some(event) = events.recv().await {
+//!     if let SupervisorEvent::Stalled { .. } = event {
+//!         jams += 1;
+//!         if jams == 3 {
+//!             intake.lock().await.brake(BrakeMode::Coast).unwrap();
+//!             break;
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! The supervisor stops once every other reference to the motor has been dropped.
+
+use alloc::sync::{Arc, Weak};
+use core::time::Duration;
+
+use vexide_async::{sleep_until, spawn};
+use vexide_core::{
+    sync::{
+        mpsc::{unbounded_channel, Receiver, UnboundedSender},
+        Mutex,
+    },
+    time::Instant,
+};
+
+use super::motor::{Motor, MotorControl, MotorError, MotorFaults};
+
+/// Drives a stalled motor in reverse for a short time to clear a jam.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JamReversal {
+    /// The magnitude of the voltage applied in reverse.
+    pub voltage: f64,
+
+    /// How long the motor is driven in reverse before its previous target is restored.
+    pub duration: Duration,
+}
+
+impl JamReversal {
+    /// Creates a policy that reverses a stalled motor at `voltage` for `duration`.
+    pub const fn new(voltage: f64, duration: Duration) -> Self {
+        Self { voltage, duration }
+    }
+}
+
+/// Lowers a motor's current limit as its temperature rises.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThermalDerating {
+    /// The temperature in °C at which the current limit starts being lowered.
+    pub start_temperature: f64,
+
+    /// The temperature in °C at which the current limit reaches its minimum.
+    pub end_temperature: f64,
+
+    /// The current limit in amps below the start temperature.
+    pub max_current_limit: f64,
+
+    /// The current limit in amps above the end temperature, or while the motor reports that it
+    /// is over temperature.
+    pub min_current_limit: f64,
+}
+
+impl ThermalDerating {
+    /// Creates a policy that lowers the current limit from `max_current_limit` to
+    /// `min_current_limit` as the temperature rises from `start_temperature` to
+    /// `end_temperature`.
+    pub const fn new(
+        start_temperature: f64,
+        end_temperature: f64,
+        max_current_limit: f64,
+        min_current_limit: f64,
+    ) -> Self {
+        Self {
+            start_temperature,
+            end_temperature,
+            max_current_limit,
+            min_current_limit,
+        }
+    }
+
+    /// Returns the current limit in amps for a motor at the given temperature.
+    pub fn current_limit(&self, temperature: f64) -> f64 {
+        if temperature <= self.start_temperature {
+            self.max_current_limit
+        } else if temperature >= self.end_temperature {
+            self.min_current_limit
+        } else {
+            let progress = (temperature - self.start_temperature)
+                / (self.end_temperature - self.start_temperature);
+            self.max_current_limit + (self.min_current_limit - self.max_current_limit) * progress
+        }
+    }
+}
+
+impl Default for ThermalDerating {
+    /// Lowers the current limit from 2.5 A to 1.0 A between 45 °C and 55 °C, just below the
+    /// temperature at which motors start limiting themselves.
+    fn default() -> Self {
+        Self::new(45.0, 55.0, 2.5, 1.0)
+    }
+}
+
+/// Something a [`MotorSupervisor`] noticed or did.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SupervisorEvent {
+    /// The motor drew at least the stall current while barely moving for the stall time.
+    ///
+    /// If [`JamReversal`] is enabled, the motor is driven in reverse right after this event.
+    Stalled {
+        /// The current drawn by the motor in amps.
+        current: f64,
+        /// The velocity of the motor in RPM.
+        velocity: f64,
+    },
+
+    /// The motor's previous target was restored after reversing it to clear a jam.
+    Resumed,
+
+    /// The motor's current limit was changed because of its temperature.
+    Derated {
+        /// The temperature of the motor in °C.
+        temperature: f64,
+        /// The new current limit in amps.
+        current_limit: f64,
+    },
+
+    /// The motor reported that it is over temperature.
+    OverTemperature {
+        /// The temperature of the motor in °C.
+        temperature: f64,
+    },
+
+    /// The motor was disconnected, so it can't be supervised until it is plugged back in.
+    Disconnected,
+}
+
+/// A reversal in progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Reversal {
+    /// The target to restore once the reversal is over.
+    target: MotorControl,
+    /// The voltage the motor is being reversed at.
+    voltage: f64,
+    /// When the reversal is over.
+    deadline: Instant,
+}
+
+/// Watches a motor for stalls, jams and overheating.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotorSupervisor {
+    /// The smallest current in amps at which the motor can be stalled. Defaults to 2.0 A.
+    pub stall_current: f64,
+
+    /// The largest velocity in RPM at which the motor can be stalled. Defaults to 5 RPM.
+    pub stall_velocity: i32,
+
+    /// How long the motor must draw the stall current without moving to be stalled. Defaults
+    /// to 250 milliseconds.
+    pub stall_time: Duration,
+
+    /// How to clear jams, if at all. Disabled by default.
+    pub jam_reversal: Option<JamReversal>,
+
+    /// How to limit the current as the motor heats up, if at all. Disabled by default.
+    pub thermal_derating: Option<ThermalDerating>,
+
+    /// How often the motor is checked. Defaults to [`Motor::DATA_READ_RATE`].
+    pub interval: Duration,
+
+    stall_start: Option<Instant>,
+    stalled: bool,
+    reversal: Option<Reversal>,
+    current_limit: Option<f64>,
+    over_temperature: bool,
+    disconnected: bool,
+}
+
+impl MotorSupervisor {
+    /// Creates a supervisor that reports stalls, without any protection policies enabled.
+    pub const fn new() -> Self {
+        Self {
+            stall_current: 2.0,
+            stall_velocity: 5,
+            stall_time: Duration::from_millis(250),
+            jam_reversal: None,
+            thermal_derating: None,
+            interval: Motor::DATA_READ_RATE,
+            stall_start: None,
+            stalled: false,
+            reversal: None,
+            current_limit: None,
+            over_temperature: false,
+            disconnected: false,
+        }
+    }
+
+    /// Sets when the motor is considered stalled: drawing at least `current` amps while
+    /// moving no faster than `velocity` RPM for `time`.
+    pub const fn with_stall_detection(
+        mut self,
+        current: f64,
+        velocity: i32,
+        time: Duration,
+    ) -> Self {
+        self.stall_current = current;
+        self.stall_velocity = velocity;
+        self.stall_time = time;
+        self
+    }
+
+    /// Reverses the motor to clear jams when it stalls.
+    pub const fn with_jam_reversal(mut self, reversal: JamReversal) -> Self {
+        self.jam_reversal = Some(reversal);
+        self
+    }
+
+    /// Lowers the motor's current limit as it heats up.
+    pub const fn with_thermal_derating(mut self, derating: ThermalDerating) -> Self {
+        self.thermal_derating = Some(derating);
+        self
+    }
+
+    /// Sets how often the motor is checked.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Starts supervising `motor` in a background task, returning a receiver for the events
+    /// it produces.
+    ///
+    /// The motor is only locked briefly each time it is checked. The task stops once every
+    /// other reference to the motor has been dropped. Events don't need to be received for
+    /// the supervisor to keep protecting the motor.
+    pub fn spawn(self, motor: &Arc<Mutex<Motor>>) -> Receiver<SupervisorEvent> {
+        let (events, receiver) = unbounded_channel();
+        spawn(self.run(Arc::downgrade(motor), events)).detach();
+        receiver
+    }
+
+    async fn run(mut self, motor: Weak<Mutex<Motor>>, events: UnboundedSender<SupervisorEvent>) {
+        let mut next_check = Instant::now();
+
+        loop {
+            let Some(motor) = motor.upgrade() else {
+                return;
+            };
+
+            let mut emit = |event| {
+                // Nobody listening for events isn't a reason to stop protecting the motor.
+                _ = events.send(event);
+            };
+
+            let result = self.check(&mut *motor.lock().await, &mut emit);
+            match result {
+                Err(MotorError::Port { .. }) => {
+                    if !self.disconnected {
+                        self.disconnected = true;
+                        emit(SupervisorEvent::Disconnected);
+                    }
+                }
+                // The motor may be briefly busy, which is fine to skip a check for.
+                Err(_) => {}
+                Ok(()) => self.disconnected = false,
+            }
+            drop(motor);
+
+            next_check += self.interval;
+            sleep_until(next_check).await;
+        }
+    }
+
+    fn check(
+        &mut self,
+        motor: &mut Motor,
+        emit: &mut impl FnMut(SupervisorEvent),
+    ) -> Result<(), MotorError> {
+        let telemetry = motor.telemetry()?;
+
+        let over_temperature = telemetry.faults.contains(MotorFaults::OVER_TEMPERATURE);
+        if over_temperature && !self.over_temperature {
+            emit(SupervisorEvent::OverTemperature {
+                temperature: telemetry.temperature,
+            });
+        }
+        self.over_temperature = over_temperature;
+
+        if let Some(derating) = self.thermal_derating {
+            let limit = if over_temperature {
+                derating.min_current_limit
+            } else {
+                derating.current_limit(telemetry.temperature)
+            };
+
+            if self.current_limit != Some(limit) {
+                motor.set_current_limit(limit)?;
+
+                // Setting the limit for the first time isn't worth reporting unless the motor
+                // is already hot.
+                if self.current_limit.is_some() || limit < derating.max_current_limit {
+                    emit(SupervisorEvent::Derated {
+                        temperature: telemetry.temperature,
+                        current_limit: limit,
+                    });
+                }
+                self.current_limit = Some(limit);
+            }
+        }
+
+        if let Some(reversal) = self.reversal {
+            if Instant::now() < reversal.deadline {
+                return Ok(());
+            }
+
+            // The previous target is only restored if nothing else has changed the motor's
+            // target in the meantime. The reversal is kept until then, so that it is retried
+            // on the next check if the motor can't be reached.
+            if motor.target()? == MotorControl::Voltage(reversal.voltage) {
+                motor.set_target(reversal.target)?;
+                emit(SupervisorEvent::Resumed);
+            }
+            self.reversal = None;
+            return Ok(());
+        }
+
+        let target = motor.target()?;
+        // Whether the motor is being driven forwards, or `None` if it isn't being driven at all.
+        let forward = match target {
+            MotorControl::Brake(_) => None,
+            MotorControl::Voltage(volts) => (volts != 0.0).then_some(volts > 0.0),
+            MotorControl::Velocity(rpm) => (rpm != 0).then_some(rpm > 0),
+            MotorControl::Position(position, _) => Some(position > telemetry.position),
+        };
+
+        let now = Instant::now();
+        let stall_velocity = f64::from(self.stall_velocity.unsigned_abs());
+        if forward.is_some()
+            && telemetry.current >= self.stall_current
+            && (-stall_velocity..=stall_velocity).contains(&telemetry.velocity)
+        {
+            self.stall_start.get_or_insert(now);
+        } else {
+            self.stall_start = None;
+            self.stalled = false;
+        }
+
+        // The stall only starts while the motor is driven, so both are set or neither is.
+        let (Some(stall_start), Some(forward)) = (self.stall_start, forward) else {
+            return Ok(());
+        };
+        if self.stalled || now - stall_start < self.stall_time {
+            return Ok(());
+        }
+        self.stalled = true;
+
+        emit(SupervisorEvent::Stalled {
+            current: telemetry.current,
+            velocity: telemetry.velocity,
+        });
+
+        if let Some(jam_reversal) = self.jam_reversal {
+            let voltage = if forward {
+                -jam_reversal.voltage
+            } else {
+                jam_reversal.voltage
+            };
+
+            motor.set_voltage(voltage)?;
+            self.reversal = Some(Reversal {
+                target,
+                voltage,
+                deadline: now + jam_reversal.duration,
+            });
+
+            // The stall timer starts over once the motor has resumed.
+            self.stall_start = None;
+            self.stalled = false;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for MotorSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
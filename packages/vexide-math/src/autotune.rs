@@ -0,0 +1,276 @@
+//! PID auto-tuning by relay feedback.
+//!
+//! A relay experiment (the Åström–Hägglund method) finds PID gains without any knowledge of
+//! the mechanism being controlled. Instead of a PID controller, a relay drives the mechanism
+//! with a fixed positive output while it is below the setpoint and a fixed negative output
+//! while it is above, which makes it oscillate steadily around the setpoint. The period and
+//! amplitude of the oscillation reveal the gain at which a proportional controller would
+//! oscillate on its own, and [`TuningRule`]s turn that into PID gains.
+//!
+//! The experiment is run on a [`RelayPlant`], such as a [`Motor`] holding a position, with
+//! [`RelayTuner::run`]:
+//!
+//! ```no_run
+//! # async fn tune(mut motor: vexide_devices::smart::Motor) {
+//! use vexide_math::autotune::{RelayTuner, TuningRule};
+//!
+//! let result = RelayTuner::new(90.0, 6.0).run(&mut motor).await.unwrap();
+//! let pid = result.gains(TuningRule::TyreusLuyben);
+//! # }
+//! ```
+//!
+//! The mechanism swings back and forth around the setpoint during the experiment, so make sure
+//! it has room to move.
+
+use core::{f64::consts::PI, fmt, time::Duration};
+
+use vexide_async::sleep_until;
+use vexide_core::time::Instant;
+use vexide_devices::smart::{motor::MotorError, Motor};
+
+use crate::pid::PidController;
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
+/// A mechanism that a relay experiment can be run on.
+pub trait RelayPlant {
+    /// The error returned when the mechanism can't be measured or driven.
+    type Error;
+
+    /// Measures the value being controlled, such as a position or heading.
+    fn measure(&mut self) -> Result<f64, Self::Error>;
+
+    /// Drives the mechanism with the given output, such as a voltage.
+    fn apply(&mut self, output: f64) -> Result<(), Self::Error>;
+}
+
+impl RelayPlant for Motor {
+    type Error = MotorError;
+
+    /// Measures the motor's position in degrees.
+    fn measure(&mut self) -> Result<f64, Self::Error> {
+        Ok(self.position()?.into_degrees())
+    }
+
+    /// Sets the motor's voltage.
+    fn apply(&mut self, output: f64) -> Result<(), Self::Error> {
+        self.set_voltage(output)
+    }
+}
+
+/// Rules for turning the results of a relay experiment into PID gains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TuningRule {
+    /// The classic Ziegler–Nichols rule, which responds quickly but overshoots noticeably.
+    ZieglerNichols,
+    /// The Tyreus–Luyben rule, which is more conservative than Ziegler–Nichols and overshoots
+    /// less, at the cost of a slower response.
+    TyreusLuyben,
+}
+
+/// The oscillation measured by a relay experiment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelayResult {
+    /// The proportional gain at which the mechanism would oscillate steadily on its own.
+    pub ultimate_gain: f64,
+    /// The period of the oscillation.
+    pub ultimate_period: Duration,
+    /// How far the measurement swung either side of its midpoint.
+    pub amplitude: f64,
+}
+
+impl RelayResult {
+    /// Returns a PID controller with gains suggested by `rule`.
+    pub fn gains(&self, rule: TuningRule) -> PidController {
+        let ku = self.ultimate_gain;
+        let pu = self.ultimate_period.as_secs_f64();
+
+        // Each rule gives a proportional gain and integral and derivative times.
+        let (kp, ti, td) = match rule {
+            TuningRule::ZieglerNichols => (0.6 * ku, pu / 2.0, pu / 8.0),
+            TuningRule::TyreusLuyben => (ku / 2.2, 2.2 * pu, pu / 6.3),
+        };
+
+        PidController::new(kp, kp / ti, kp * td)
+    }
+}
+
+/// Errors that can occur while running a relay experiment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayTuneError<E> {
+    /// The mechanism could not be measured or driven.
+    Plant(E),
+    /// The mechanism didn't oscillate steadily before the experiment timed out.
+    TimedOut,
+    /// The measurement didn't swing further from its midpoint than the hysteresis, so the
+    /// ultimate gain couldn't be measured.
+    NoOscillation,
+}
+
+impl<E: fmt::Display> fmt::Display for RelayTuneError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Plant(error) => write!(f, "failed to measure or drive the mechanism: {error}"),
+            Self::TimedOut => f.write_str("the relay experiment timed out"),
+            Self::NoOscillation => {
+                f.write_str("the mechanism didn't oscillate further than the hysteresis")
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for RelayTuneError<E> {}
+
+/// Runs a relay feedback experiment to find PID gains.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelayTuner {
+    /// The value the mechanism oscillates around.
+    pub setpoint: f64,
+    /// The output applied either side of the setpoint.
+    pub relay_output: f64,
+    /// How far the measurement must cross the setpoint before the relay switches.
+    ///
+    /// A little hysteresis stops noise from switching the relay back and forth when the
+    /// measurement is near the setpoint. Defaults to `0.0`.
+    pub hysteresis: f64,
+    /// The number of oscillations averaged for the result. Defaults to 4.
+    pub cycles: usize,
+    /// How often the mechanism is measured. Defaults to 10 milliseconds.
+    pub sample_interval: Duration,
+    /// How long the experiment can run before it is abandoned. Defaults to 30 seconds.
+    pub timeout: Duration,
+}
+
+impl RelayTuner {
+    /// Creates an experiment oscillating around `setpoint`, driving the mechanism with
+    /// `relay_output` either side of it.
+    pub const fn new(setpoint: f64, relay_output: f64) -> Self {
+        Self {
+            setpoint,
+            relay_output,
+            hysteresis: 0.0,
+            cycles: 4,
+            sample_interval: Duration::from_millis(10),
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Sets how far the measurement must cross the setpoint before the relay switches.
+    pub const fn with_hysteresis(mut self, hysteresis: f64) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Sets the number of oscillations averaged for the result.
+    pub const fn with_cycles(mut self, cycles: usize) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
+    /// Sets how often the mechanism is measured.
+    pub const fn with_sample_interval(mut self, interval: Duration) -> Self {
+        self.sample_interval = interval;
+        self
+    }
+
+    /// Sets how long the experiment can run before it is abandoned.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Runs the experiment on `plant`, returning the measured oscillation.
+    ///
+    /// The first oscillation is ignored, since the mechanism is still settling into a steady
+    /// rhythm. The plant's output is set to zero once the experiment ends, even if it fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`RelayTuner::cycles`] is 0.
+    pub async fn run<P: RelayPlant>(
+        &self,
+        plant: &mut P,
+    ) -> Result<RelayResult, RelayTuneError<P::Error>> {
+        assert!(self.cycles > 0, "relay experiments need at least one cycle");
+
+        let result = self.oscillate(plant).await;
+        let stopped = plant.apply(0.0).map_err(RelayTuneError::Plant);
+        let result = result?;
+        stopped?;
+        Ok(result)
+    }
+
+    async fn oscillate<P: RelayPlant>(
+        &self,
+        plant: &mut P,
+    ) -> Result<RelayResult, RelayTuneError<P::Error>> {
+        let start = Instant::now();
+        let mut next_sample = start;
+
+        let mut output_high = plant.measure().map_err(RelayTuneError::Plant)? < self.setpoint;
+        let mut last_rising_edge: Option<Instant> = None;
+        let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+
+        // The first full cycle is skipped.
+        let mut cycles_seen = 0;
+        let (mut total_period, mut total_amplitude) = (Duration::ZERO, 0.0);
+
+        loop {
+            let now = Instant::now();
+            if now.duration_since(start) >= self.timeout {
+                return Err(RelayTuneError::TimedOut);
+            }
+
+            let measurement = plant.measure().map_err(RelayTuneError::Plant)?;
+            min = min.min(measurement);
+            max = max.max(measurement);
+
+            let error = self.setpoint - measurement;
+            if !output_high && error > self.hysteresis {
+                output_high = true;
+
+                // A full oscillation runs between each time the relay switches high.
+                if let Some(last) = last_rising_edge.replace(now) {
+                    cycles_seen += 1;
+                    if cycles_seen > 1 {
+                        total_period += now.duration_since(last);
+                        total_amplitude += (max - min) / 2.0;
+                    }
+                    if cycles_seen > self.cycles {
+                        break;
+                    }
+                }
+                (min, max) = (measurement, measurement);
+            } else if output_high && error < -self.hysteresis {
+                output_high = false;
+            }
+
+            let output = if output_high {
+                self.relay_output
+            } else {
+                -self.relay_output
+            };
+            plant.apply(output).map_err(RelayTuneError::Plant)?;
+
+            next_sample += self.sample_interval;
+            sleep_until(next_sample).await;
+        }
+
+        let cycles = self.cycles as f64;
+        let amplitude = total_amplitude / cycles;
+        if amplitude <= self.hysteresis.abs() {
+            return Err(RelayTuneError::NoOscillation);
+        }
+
+        // Describing function analysis of a relay with hysteresis. Without hysteresis, this is
+        // the usual 4d / πa.
+        let ultimate_gain = 4.0 * self.relay_output
+            / (PI * (amplitude * amplitude - self.hysteresis * self.hysteresis).sqrt());
+
+        Ok(RelayResult {
+            ultimate_gain,
+            ultimate_period: total_period.div_f64(cycles),
+            amplitude,
+        })
+    }
+}
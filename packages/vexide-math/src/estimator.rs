@@ -0,0 +1,407 @@
+//! Pose estimation by sensor fusion.
+//!
+//! Wheel odometry is smooth and responsive but slowly drifts away from the robot's true pose,
+//! while absolute sensors such as a GPS sensor don't drift but are noisy and update less often.
+//! A [`PoseEstimator`] combines both using an extended Kalman filter, weighing each reading by
+//! how much it can be trusted.
+//!
+//! Odometry is added as the movement since the previous update, such as the
+//! [`Transform2d::between`] the last two poses from an [`Odometry`](crate::odometry::Odometry)
+//! tracker. Headings and positions measured directly are added as they arrive. How much each
+//! input is trusted is given by its covariance, where larger values mean a less accurate
+//! reading.
+//!
+//! Every input carries the [`Instant`] it was measured at. Readings that arrive late, such as
+//! a GPS position that took time to calculate, are applied at the time they were measured and
+//! all later inputs are replayed on top of them, as long as they are newer than the estimator's
+//! [history length](PoseEstimator::with_history_length).
+
+use alloc::collections::VecDeque;
+use core::time::Duration;
+
+use vexide_core::time::Instant;
+
+use crate::geometry::{Pose2d, Rotation2d, Transform2d, Vec2};
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
+type Matrix3 = [[f64; 3]; 3];
+
+/// A reading added to a [`PoseEstimator`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Input {
+    Odometry {
+        delta: Transform2d,
+        covariance: Matrix3,
+    },
+    Heading {
+        heading: Rotation2d,
+        variance: f64,
+    },
+    Position {
+        position: Vec2,
+        covariance: [[f64; 2]; 2],
+    },
+}
+
+/// An input, and the estimate after it was applied.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Entry {
+    timestamp: Instant,
+    input: Input,
+    pose: Pose2d,
+    covariance: Matrix3,
+}
+
+/// Estimates the robot's pose by fusing odometry with heading and position measurements.
+#[derive(Debug, Clone)]
+pub struct PoseEstimator {
+    /// How long inputs are remembered for, so that delayed measurements can be applied.
+    pub history_length: Duration,
+    /// The estimate before the oldest remembered input.
+    base_pose: Pose2d,
+    base_covariance: Matrix3,
+    base_time: Option<Instant>,
+    history: VecDeque<Entry>,
+}
+
+impl PoseEstimator {
+    /// Creates an estimator starting at `pose`, with the given covariance of the x position, y
+    /// position and heading in radians.
+    ///
+    /// Inputs are remembered for one second by default.
+    pub const fn new(pose: Pose2d, covariance: [[f64; 3]; 3]) -> Self {
+        Self {
+            history_length: Duration::from_secs(1),
+            base_pose: pose,
+            base_covariance: covariance,
+            base_time: None,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Sets how long inputs are remembered for. Measurements older than this are ignored.
+    pub const fn with_history_length(mut self, length: Duration) -> Self {
+        self.history_length = length;
+        self
+    }
+
+    /// Returns the current estimate of the robot's pose.
+    pub fn pose(&self) -> Pose2d {
+        self.history
+            .back()
+            .map_or(self.base_pose, |entry| entry.pose)
+    }
+
+    /// Returns the covariance of the current estimate's x position, y position and heading.
+    pub fn covariance(&self) -> [[f64; 3]; 3] {
+        self.history
+            .back()
+            .map_or(self.base_covariance, |entry| entry.covariance)
+    }
+
+    /// Resets the estimate to `pose` with the given covariance, forgetting all previous inputs.
+    pub fn reset(&mut self, pose: Pose2d, covariance: [[f64; 3]; 3]) {
+        self.base_pose = pose;
+        self.base_covariance = covariance;
+        self.base_time = None;
+        self.history.clear();
+    }
+
+    /// Adds the robot's movement since the previous odometry update, measured at `timestamp`.
+    ///
+    /// `covariance` describes the error in the movement's x, y and heading components, measured
+    /// in the robot's frame at the start of the movement.
+    pub fn add_odometry(
+        &mut self,
+        timestamp: Instant,
+        delta: Transform2d,
+        covariance: [[f64; 3]; 3],
+    ) {
+        self.add(timestamp, Input::Odometry { delta, covariance });
+    }
+
+    /// Adds a measurement of the robot's heading on the field, taken at `timestamp`, with the
+    /// given variance in radians squared.
+    pub fn add_heading(&mut self, timestamp: Instant, heading: Rotation2d, variance: f64) {
+        self.add(timestamp, Input::Heading { heading, variance });
+    }
+
+    /// Adds a measurement of the robot's position on the field, taken at `timestamp`, with the
+    /// given covariance of its x and y components.
+    pub fn add_position(&mut self, timestamp: Instant, position: Vec2, covariance: [[f64; 2]; 2]) {
+        self.add(
+            timestamp,
+            Input::Position {
+                position,
+                covariance,
+            },
+        );
+    }
+
+    fn add(&mut self, timestamp: Instant, input: Input) {
+        if self
+            .base_time
+            .is_some_and(|base_time| timestamp < base_time)
+        {
+            // The estimate from before this measurement has already been forgotten.
+            return;
+        }
+
+        // Inputs are kept in the order they were measured, so a late measurement is inserted
+        // in the past and everything after it is recalculated.
+        let index = self
+            .history
+            .partition_point(|entry| entry.timestamp <= timestamp);
+        self.history.insert(
+            index,
+            Entry {
+                timestamp,
+                input,
+                pose: Pose2d::ORIGIN,
+                covariance: [[0.0; 3]; 3],
+            },
+        );
+        self.replay_from(index);
+        self.forget_old_inputs();
+    }
+
+    /// Recalculates the estimate after every input from `index` onwards.
+    fn replay_from(&mut self, index: usize) {
+        let (mut pose, mut covariance) = match index.checked_sub(1) {
+            Some(previous) => (
+                self.history[previous].pose,
+                self.history[previous].covariance,
+            ),
+            None => (self.base_pose, self.base_covariance),
+        };
+
+        for entry in self.history.range_mut(index..) {
+            match entry.input {
+                Input::Odometry {
+                    delta,
+                    covariance: noise,
+                } => predict(&mut pose, &mut covariance, delta, noise),
+                Input::Heading { heading, variance } => {
+                    correct_heading(&mut pose, &mut covariance, heading, variance);
+                }
+                Input::Position {
+                    position,
+                    covariance: noise,
+                } => correct_position(&mut pose, &mut covariance, position, noise),
+            }
+            entry.pose = pose;
+            entry.covariance = covariance;
+        }
+    }
+
+    /// Drops inputs older than the history length, folding them into the base estimate.
+    fn forget_old_inputs(&mut self) {
+        let Some(cutoff) = self
+            .history
+            .back()
+            .and_then(|newest| newest.timestamp.checked_sub(self.history_length))
+        else {
+            return;
+        };
+
+        while let Some(entry) = self.history.front() {
+            if entry.timestamp >= cutoff {
+                break;
+            }
+            self.base_pose = entry.pose;
+            self.base_covariance = entry.covariance;
+            self.base_time = Some(entry.timestamp);
+            self.history.pop_front();
+        }
+    }
+}
+
+/// Moves the estimate by an odometry delta, growing its uncertainty by the odometry's noise.
+fn predict(pose: &mut Pose2d, covariance: &mut Matrix3, delta: Transform2d, noise: Matrix3) {
+    let (sin, cos) = (pose.heading.sin(), pose.heading.cos());
+    let Vec2 { x: dx, y: dy } = delta.translation;
+
+    // Jacobian of the new pose with respect to the old one. Turning the robot swings the
+    // movement around, so the position depends on the heading.
+    let f = [
+        [1.0, 0.0, -sin * dx - cos * dy],
+        [0.0, 1.0, cos * dx - sin * dy],
+        [0.0, 0.0, 1.0],
+    ];
+    // Jacobian of the new pose with respect to the movement, which rotates it onto the field.
+    let g = [[cos, -sin, 0.0], [sin, cos, 0.0], [0.0, 0.0, 1.0]];
+
+    *pose = pose.transform_by(delta);
+    *covariance = add(
+        multiply(multiply(f, *covariance), transpose(f)),
+        multiply(multiply(g, noise), transpose(g)),
+    );
+}
+
+/// Corrects the estimate with a direct measurement of the heading.
+fn correct_heading(
+    pose: &mut Pose2d,
+    covariance: &mut Matrix3,
+    heading: Rotation2d,
+    variance: f64,
+) {
+    let innovation_variance = covariance[2][2] + variance;
+    if innovation_variance <= 0.0 {
+        return;
+    }
+
+    let innovation = pose.heading.shortest_difference(heading).radians();
+    let gain = [
+        covariance[0][2] / innovation_variance,
+        covariance[1][2] / innovation_variance,
+        covariance[2][2] / innovation_variance,
+    ];
+
+    pose.position += Vec2::new(gain[0], gain[1]) * innovation;
+    pose.heading += Rotation2d::from_radians(gain[2] * innovation);
+
+    let row = covariance[2];
+    for (i, gain) in gain.iter().enumerate() {
+        for (j, value) in row.iter().enumerate() {
+            covariance[i][j] -= gain * value;
+        }
+    }
+}
+
+/// Corrects the estimate with a direct measurement of the position.
+fn correct_position(
+    pose: &mut Pose2d,
+    covariance: &mut Matrix3,
+    position: Vec2,
+    noise: [[f64; 2]; 2],
+) {
+    let s = [
+        [
+            covariance[0][0] + noise[0][0],
+            covariance[0][1] + noise[0][1],
+        ],
+        [
+            covariance[1][0] + noise[1][0],
+            covariance[1][1] + noise[1][1],
+        ],
+    ];
+    let determinant = s[0][0] * s[1][1] - s[0][1] * s[1][0];
+    if determinant.abs() < f64::EPSILON {
+        return;
+    }
+    let s_inverse = [
+        [s[1][1] / determinant, -s[0][1] / determinant],
+        [-s[1][0] / determinant, s[0][0] / determinant],
+    ];
+
+    // The gain maps an error in position to a correction of the whole pose.
+    let mut gain = [[0.0; 2]; 3];
+    for (i, row) in gain.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = covariance[i][0] * s_inverse[0][j] + covariance[i][1] * s_inverse[1][j];
+        }
+    }
+
+    let innovation = position - pose.position;
+    let correction = |row: [f64; 2]| row[0] * innovation.x + row[1] * innovation.y;
+    pose.position += Vec2::new(correction(gain[0]), correction(gain[1]));
+    pose.heading += Rotation2d::from_radians(correction(gain[2]));
+
+    let rows = [covariance[0], covariance[1]];
+    for (i, gain) in gain.iter().enumerate() {
+        for j in 0..3 {
+            covariance[i][j] -= gain[0] * rows[0][j] + gain[1] * rows[1][j];
+        }
+    }
+}
+
+fn multiply(a: Matrix3, b: Matrix3) -> Matrix3 {
+    let mut product = [[0.0; 3]; 3];
+    for (i, row) in product.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    product
+}
+
+fn transpose(a: Matrix3) -> Matrix3 {
+    let mut transposed = [[0.0; 3]; 3];
+    for (i, row) in transposed.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = a[j][i];
+        }
+    }
+    transposed
+}
+
+fn add(a: Matrix3, b: Matrix3) -> Matrix3 {
+    let mut sum = a;
+    for (i, row) in sum.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value += b[i][j];
+        }
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ODOMETRY_NOISE: Matrix3 = [[0.01, 0.0, 0.0], [0.0, 0.01, 0.0], [0.0, 0.0, 0.001]];
+    const POSITION_NOISE: [[f64; 2]; 2] = [[0.1, 0.0], [0.0, 0.1]];
+
+    fn estimator() -> PoseEstimator {
+        PoseEstimator::new(
+            Pose2d::ORIGIN,
+            [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 0.1]],
+        )
+        .with_history_length(Duration::from_millis(100))
+    }
+
+    const fn forward(distance: f64) -> Transform2d {
+        Transform2d::new(Vec2::new(distance, 0.0), Rotation2d::ZERO)
+    }
+
+    #[test]
+    fn late_measurements_are_replayed_in_order() {
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        let mut in_order = estimator();
+        in_order.add_odometry(at(10), forward(1.0), ODOMETRY_NOISE);
+        in_order.add_position(at(20), Vec2::new(2.0, 0.5), POSITION_NOISE);
+        in_order.add_odometry(at(30), forward(1.0), ODOMETRY_NOISE);
+
+        let mut late = estimator();
+        late.add_odometry(at(10), forward(1.0), ODOMETRY_NOISE);
+        late.add_odometry(at(30), forward(1.0), ODOMETRY_NOISE);
+        let before = late.pose();
+        late.add_position(at(20), Vec2::new(2.0, 0.5), POSITION_NOISE);
+
+        assert_ne!(late.pose(), before);
+        assert_eq!(late.pose(), in_order.pose());
+        assert_eq!(late.covariance(), in_order.covariance());
+    }
+
+    #[test]
+    fn inputs_older_than_the_history_are_ignored() {
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        let mut estimator = estimator();
+        estimator.add_odometry(at(10), forward(1.0), ODOMETRY_NOISE);
+        estimator.add_odometry(at(200), forward(1.0), ODOMETRY_NOISE);
+        let pose = estimator.pose();
+
+        // The odometry at 10 ms has been forgotten, so nothing can be applied before it.
+        estimator.add_position(at(5), Vec2::new(5.0, 5.0), POSITION_NOISE);
+        assert_eq!(estimator.pose(), pose);
+
+        // Measurements within the history are still applied.
+        estimator.add_position(at(150), Vec2::new(5.0, 5.0), POSITION_NOISE);
+        assert_ne!(estimator.pose(), pose);
+    }
+}
@@ -1,6 +1,16 @@
 //! Simple feedforward controller for motors.
 //! Computes the voltage to maintain an idealized DC motor in a certain state.
 //! Uses this feedforward model: V = Kₛ sign(ω) + Kᵥ ω + Kₐ α
+//!
+//! Mechanisms that lift a load against gravity also need a gravity term:
+//!
+//! - [`ElevatorFeedforward`] adds a constant K_g to hold up a load that moves vertically.
+//! - [`ArmFeedforward`] adds K_g cos θ to hold up an arm at angle θ from horizontal.
+//!
+//! Gains for these models can be measured with [`sysid`](crate::sysid).
+
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
 
 /// Feedforward controller for motor control.
 ///
@@ -58,3 +68,64 @@ impl MotorFeedforwardController {
         self.ks * num::signum(target) + self.kv * target + self.ka * target_acceleration
     }
 }
+
+/// Feedforward for a mechanism that moves a load straight up and down, such as an elevator or
+/// lift.
+///
+/// Uses this feedforward model: V = K_g + Kₛ sign(v) + Kᵥ v + Kₐ a
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ElevatorFeedforward {
+    /// Voltage needed to overcome static friction.
+    pub ks: f64,
+    /// Voltage needed to hold the load up against gravity.
+    pub kg: f64,
+    /// Voltage needed per unit of velocity.
+    pub kv: f64,
+    /// Voltage needed per unit of acceleration.
+    pub ka: f64,
+}
+
+impl ElevatorFeedforward {
+    /// Creates a new feedforward model with the given gains.
+    pub const fn new(ks: f64, kg: f64, kv: f64, ka: f64) -> Self {
+        Self { ks, kg, kv, ka }
+    }
+
+    /// Calculates the voltage needed to move at `velocity` while accelerating at
+    /// `acceleration`, both positive upwards.
+    pub fn calculate(&self, velocity: f64, acceleration: f64) -> f64 {
+        self.kg + self.ks * num::signum(velocity) + self.kv * velocity + self.ka * acceleration
+    }
+}
+
+/// Feedforward for an arm that rotates a load around a horizontal pivot.
+///
+/// Gravity pulls hardest on the arm when it is horizontal and not at all when it is vertical.
+/// Uses this feedforward model: V = K_g cos θ + Kₛ sign(ω) + Kᵥ ω + Kₐ α
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ArmFeedforward {
+    /// Voltage needed to overcome static friction.
+    pub ks: f64,
+    /// Voltage needed to hold the arm horizontal against gravity.
+    pub kg: f64,
+    /// Voltage needed per radian per second of angular velocity.
+    pub kv: f64,
+    /// Voltage needed per radian per second squared of angular acceleration.
+    pub ka: f64,
+}
+
+impl ArmFeedforward {
+    /// Creates a new feedforward model with the given gains.
+    pub const fn new(ks: f64, kg: f64, kv: f64, ka: f64) -> Self {
+        Self { ks, kg, kv, ka }
+    }
+
+    /// Calculates the voltage needed to move the arm at `velocity` while accelerating at
+    /// `acceleration` when it is `angle` radians above horizontal.
+    pub fn calculate(&self, angle: f64, velocity: f64, acceleration: f64) -> f64 {
+        self.kg * angle.cos()
+            + self.ks * num::signum(velocity)
+            + self.kv * velocity
+            + self.ka * acceleration
+    }
+}
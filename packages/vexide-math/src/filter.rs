@@ -0,0 +1,456 @@
+//! Signal filters.
+//!
+//! Filters smooth out noisy sensor readings, such as those from
+//! [`DistanceSensor::distance`](vexide_devices::smart::DistanceSensor::distance) or
+//! [`Motor::velocity`](vexide_devices::smart::Motor::velocity). Each filter is updated with
+//! every new reading through the [`Filter`] trait and returns the filtered value.
+//!
+//! Filters can be combined with [`Filter::chain`], which feeds the output of one filter into the
+//! next. For example, a median filter followed by a low-pass filter first rejects outliers and
+//! then smooths what is left:
+//!
+//! ```no_run
+//! use vexide_math::filter::{Filter, LowPassFilter, MedianFilter};
+//!
+//! let mut filter = MedianFilter::<5>::new().chain(LowPassFilter::new(0.2));
+//! let smoothed = filter.update(42.0);
+//! ```
+//!
+//! None of the filters allocate. Filters that remember several past readings, such as
+//! [`MovingAverage`] and [`MedianFilter`], take the number of readings as a const generic.
+
+use core::time::Duration;
+
+use vexide_core::time::Instant;
+
+/// A filter that processes a stream of readings.
+pub trait Filter {
+    /// The type of readings the filter takes.
+    type Input;
+    /// The type of values the filter produces.
+    type Output;
+
+    /// Adds a new reading to the filter, returning the filtered value.
+    fn update(&mut self, input: Self::Input) -> Self::Output;
+
+    /// Clears the filter's history, as if it had just been created.
+    fn reset(&mut self);
+
+    /// Creates a filter that passes the output of this filter through `next`.
+    fn chain<F: Filter<Input = Self::Output>>(self, next: F) -> Chain<Self, F>
+    where
+        Self: Sized,
+    {
+        Chain { first: self, next }
+    }
+}
+
+/// Two filters applied one after the other, created with [`Filter::chain`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Chain<A, B> {
+    first: A,
+    next: B,
+}
+
+impl<A: Filter, B: Filter<Input = A::Output>> Filter for Chain<A, B> {
+    type Input = A::Input;
+    type Output = B::Output;
+
+    fn update(&mut self, input: Self::Input) -> Self::Output {
+        self.next.update(self.first.update(input))
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.next.reset();
+    }
+}
+
+/// An exponential low-pass filter, which moves part of the way towards each new reading.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LowPassFilter {
+    /// How far the output moves towards each new reading, in the range `(0.0, 1.0]`.
+    ///
+    /// Lower values smooth out more noise at the cost of responding more slowly.
+    pub alpha: f64,
+    value: Option<f64>,
+}
+
+impl LowPassFilter {
+    /// Creates a filter that moves `alpha` of the way towards each new reading.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` isn't in the range `(0.0, 1.0]`.
+    pub fn new(alpha: f64) -> Self {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "low-pass filter alpha must be in the range (0.0, 1.0]"
+        );
+        Self { alpha, value: None }
+    }
+}
+
+impl Filter for LowPassFilter {
+    type Input = f64;
+    type Output = f64;
+
+    /// Adds a new reading to the filter, returning the filtered value.
+    ///
+    /// The first reading is returned unchanged.
+    fn update(&mut self, input: f64) -> f64 {
+        let value = match self.value {
+            Some(value) => value + self.alpha * (input - value),
+            None => input,
+        };
+        self.value = Some(value);
+        value
+    }
+
+    fn reset(&mut self) {
+        self.value = None;
+    }
+}
+
+/// A fixed number of the most recent readings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Window<const N: usize> {
+    readings: [f64; N],
+    len: usize,
+    next: usize,
+}
+
+impl<const N: usize> Window<N> {
+    const fn new() -> Self {
+        assert!(N > 0, "filter windows must hold at least one reading");
+        Self {
+            readings: [0.0; N],
+            len: 0,
+            next: 0,
+        }
+    }
+
+    /// Adds a reading, replacing the oldest one once the window is full.
+    fn push(&mut self, reading: f64) {
+        self.readings[self.next] = reading;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Returns the readings in the window, in no particular order.
+    fn readings(&self) -> &[f64] {
+        &self.readings[..self.len]
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+        self.next = 0;
+    }
+}
+
+/// A filter that averages the last `N` readings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovingAverage<const N: usize> {
+    window: Window<N>,
+}
+
+impl<const N: usize> MovingAverage<N> {
+    /// Creates a filter averaging the last `N` readings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    pub const fn new() -> Self {
+        Self {
+            window: Window::new(),
+        }
+    }
+}
+
+impl<const N: usize> Default for MovingAverage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Filter for MovingAverage<N> {
+    type Input = f64;
+    type Output = f64;
+
+    /// Adds a new reading to the filter, returning the filtered value.
+    ///
+    /// Until `N` readings have been added, all readings so far are averaged.
+    fn update(&mut self, input: f64) -> f64 {
+        self.window.push(input);
+        let readings = self.window.readings();
+        readings.iter().sum::<f64>() / readings.len() as f64
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+/// A filter that returns the median of the last `N` readings.
+///
+/// Unlike averaging filters, a median filter ignores occasional readings that are far off from
+/// the rest, such as a distance sensor briefly seeing past an object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MedianFilter<const N: usize> {
+    window: Window<N>,
+}
+
+impl<const N: usize> MedianFilter<N> {
+    /// Creates a filter returning the median of the last `N` readings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    pub const fn new() -> Self {
+        Self {
+            window: Window::new(),
+        }
+    }
+}
+
+impl<const N: usize> Default for MedianFilter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Filter for MedianFilter<N> {
+    type Input = f64;
+    type Output = f64;
+
+    /// Adds a new reading to the filter, returning the filtered value.
+    ///
+    /// When an even number of readings is held, the two middle readings are averaged.
+    fn update(&mut self, input: f64) -> f64 {
+        self.window.push(input);
+
+        let mut sorted = [0.0; N];
+        let sorted = &mut sorted[..self.window.len];
+        sorted.copy_from_slice(self.window.readings());
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let middle = sorted.len() / 2;
+        if sorted.len() % 2 == 0 {
+            (sorted[middle - 1] + sorted[middle]) / 2.0
+        } else {
+            sorted[middle]
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+/// A one-dimensional Kalman filter for a value that is expected to stay roughly constant
+/// between readings.
+///
+/// The filter weighs each new reading against its current estimate based on how much the value
+/// is expected to change between readings and how noisy the readings are.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KalmanFilter {
+    /// The variance of how much the true value changes between readings.
+    pub process_noise: f64,
+    /// The variance of the noise in each reading.
+    pub measurement_noise: f64,
+    estimate: Option<f64>,
+    error_variance: f64,
+}
+
+impl KalmanFilter {
+    /// Creates a filter with the given process and measurement noise variances.
+    pub const fn new(process_noise: f64, measurement_noise: f64) -> Self {
+        Self {
+            process_noise,
+            measurement_noise,
+            estimate: None,
+            error_variance: 0.0,
+        }
+    }
+
+    /// Returns the variance of the current estimate, which shrinks as more readings are added.
+    pub const fn error_variance(&self) -> f64 {
+        self.error_variance
+    }
+}
+
+impl Filter for KalmanFilter {
+    type Input = f64;
+    type Output = f64;
+
+    /// Adds a new reading to the filter, returning the filtered value.
+    ///
+    /// The first reading is returned unchanged.
+    fn update(&mut self, input: f64) -> f64 {
+        let Some(estimate) = self.estimate else {
+            self.estimate = Some(input);
+            self.error_variance = self.measurement_noise;
+            return input;
+        };
+
+        // Predict: the value may have drifted since the last reading.
+        let predicted_variance = self.error_variance + self.process_noise;
+
+        // Correct: move towards the reading in proportion to how much it can be trusted.
+        let gain = predicted_variance / (predicted_variance + self.measurement_noise);
+        let estimate = estimate + gain * (input - estimate);
+        self.error_variance = (1.0 - gain) * predicted_variance;
+        self.estimate = Some(estimate);
+
+        estimate
+    }
+
+    fn reset(&mut self) {
+        self.estimate = None;
+        self.error_variance = 0.0;
+    }
+}
+
+/// A filter that only changes its output once the input has held a new value for a period of
+/// time.
+///
+/// This is useful for ignoring brief glitches from switches and buttons.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Debouncer<T> {
+    /// How long the input must hold a new value before the output changes.
+    pub debounce_time: Duration,
+    value: Option<T>,
+    pending: Option<(T, Duration)>,
+    last_time: Option<Instant>,
+}
+
+impl<T: Clone + PartialEq> Debouncer<T> {
+    /// Creates a debouncer that waits for the input to hold a new value for `debounce_time`.
+    pub const fn new(debounce_time: Duration) -> Self {
+        Self {
+            debounce_time,
+            value: None,
+            pending: None,
+            last_time: None,
+        }
+    }
+
+    /// Adds a new reading to the debouncer, given the time since the last update.
+    pub fn update_with_dt(&mut self, input: T, dt: Duration) -> T {
+        let Some(value) = &self.value else {
+            self.value = Some(input.clone());
+            return input;
+        };
+
+        if input == *value {
+            self.pending = None;
+            return input;
+        }
+
+        let held_for = match &self.pending {
+            Some((pending, held_for)) if *pending == input => *held_for + dt,
+            _ => Duration::ZERO,
+        };
+
+        if held_for >= self.debounce_time {
+            self.pending = None;
+            self.value = Some(input.clone());
+            input
+        } else {
+            self.pending = Some((input, held_for));
+            value.clone()
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> Filter for Debouncer<T> {
+    type Input = T;
+    type Output = T;
+
+    /// Adds a new reading to the debouncer, using the time since the last update.
+    ///
+    /// The first reading is returned unchanged.
+    fn update(&mut self, input: T) -> T {
+        let now = Instant::now();
+        let dt = self
+            .last_time
+            .map_or(Duration::ZERO, |last_time| now.duration_since(last_time));
+        self.last_time = Some(now);
+
+        self.update_with_dt(input, dt)
+    }
+
+    fn reset(&mut self) {
+        self.value = None;
+        self.pending = None;
+        self.last_time = None;
+    }
+}
+
+/// A filter that limits how quickly its output can change.
+///
+/// Limiting the rate at which a drivetrain's target speed changes keeps the robot from tipping
+/// or its wheels from slipping under sudden acceleration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlewRateLimiter {
+    /// The largest amount the output can increase by per second.
+    pub rising_rate: f64,
+    /// The largest amount the output can decrease by per second.
+    pub falling_rate: f64,
+    value: Option<f64>,
+    last_time: Option<Instant>,
+}
+
+impl SlewRateLimiter {
+    /// Creates a limiter that changes its output by at most `rate` per second.
+    pub const fn new(rate: f64) -> Self {
+        Self {
+            rising_rate: rate,
+            falling_rate: rate,
+            value: None,
+            last_time: None,
+        }
+    }
+
+    /// Sets a different limit for how quickly the output can decrease.
+    pub const fn with_falling_rate(mut self, rate: f64) -> Self {
+        self.falling_rate = rate;
+        self
+    }
+
+    /// Adds a new reading to the limiter, given the time since the last update.
+    pub fn update_with_dt(&mut self, input: f64, dt: Duration) -> f64 {
+        let value = match self.value {
+            Some(value) => {
+                let dt = dt.as_secs_f64();
+                value + (input - value).clamp(-self.falling_rate * dt, self.rising_rate * dt)
+            }
+            None => input,
+        };
+        self.value = Some(value);
+        value
+    }
+}
+
+impl Filter for SlewRateLimiter {
+    type Input = f64;
+    type Output = f64;
+
+    /// Adds a new reading to the limiter, using the time since the last update.
+    ///
+    /// The first reading is returned unchanged.
+    fn update(&mut self, input: f64) -> f64 {
+        let now = Instant::now();
+        let dt = self
+            .last_time
+            .map_or(Duration::ZERO, |last_time| now.duration_since(last_time));
+        self.last_time = Some(now);
+
+        self.update_with_dt(input, dt)
+    }
+
+    fn reset(&mut self) {
+        self.value = None;
+        self.last_time = None;
+    }
+}
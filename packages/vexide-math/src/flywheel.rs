@@ -0,0 +1,345 @@
+//! Flywheel velocity control.
+//!
+//! A [`Flywheel`] spins a shooter up to a target velocity and keeps it there, reporting when it
+//! is at speed and ready to shoot. Launching a game piece slows the flywheel down suddenly,
+//! which is detected as a shot, and the flywheel is reported as recovering until it is back at
+//! speed.
+//!
+//! The voltage applied to the flywheel is chosen by a [`FlywheelController`]:
+//!
+//! - [`BangBang`] applies full voltage below the target and none above it. It recovers from
+//!   shots as fast as possible, but oscillates around the target.
+//! - [`TakeBackHalf`] integrates the error and halves its output each time the target is
+//!   crossed, settling on the voltage that holds the target with only a single gain to tune.
+//! - [`FeedforwardPid`] predicts the voltage needed from a feedforward model and corrects the
+//!   remaining error with a [`PidController`].
+//!
+//! Velocities can be measured with [`Motor::velocity`] or a rotation sensor, in any units, as
+//! long as the target uses the same units. The output is a voltage for [`Motor::set_voltage`].
+//!
+//! [`Motor::velocity`]: vexide_devices::smart::Motor::velocity
+//! [`Motor::set_voltage`]: vexide_devices::smart::Motor::set_voltage
+
+use core::time::Duration;
+
+use vexide_core::time::Instant;
+use vexide_devices::smart::Motor;
+
+use crate::pid::PidController;
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
+/// Chooses the voltage to apply to a flywheel to reach a target velocity.
+pub trait FlywheelController {
+    /// Calculates the voltage to apply given the target velocity, the measured velocity and the
+    /// time since the last update.
+    fn calculate(&mut self, target: f64, velocity: f64, dt: Duration) -> f64;
+
+    /// Clears any state built up by previous updates.
+    fn reset(&mut self);
+}
+
+/// A controller that applies full voltage below the target velocity and a lower voltage above
+/// it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BangBang {
+    /// The voltage applied below the target velocity.
+    pub max_voltage: f64,
+    /// The voltage applied at or above the target velocity. Defaults to `0.0`, which lets the
+    /// flywheel coast.
+    pub hold_voltage: f64,
+}
+
+impl BangBang {
+    /// Creates a controller applying `max_voltage` below the target velocity.
+    pub const fn new(max_voltage: f64) -> Self {
+        Self {
+            max_voltage,
+            hold_voltage: 0.0,
+        }
+    }
+
+    /// Sets the voltage applied at or above the target velocity.
+    pub const fn with_hold_voltage(mut self, voltage: f64) -> Self {
+        self.hold_voltage = voltage;
+        self
+    }
+}
+
+impl Default for BangBang {
+    fn default() -> Self {
+        Self::new(Motor::MAX_VOLTAGE)
+    }
+}
+
+impl FlywheelController for BangBang {
+    fn calculate(&mut self, target: f64, velocity: f64, _dt: Duration) -> f64 {
+        if target == 0.0 {
+            0.0
+        } else if velocity.abs() < target.abs() {
+            self.max_voltage.copysign(target)
+        } else {
+            self.hold_voltage.copysign(target)
+        }
+    }
+
+    fn reset(&mut self) {}
+}
+
+/// A take-back-half controller.
+///
+/// The output grows with the integral of the error. Each time the velocity crosses the target,
+/// the output is set halfway between its current value and its value at the previous crossing,
+/// homing in on the voltage that holds the target velocity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TakeBackHalf {
+    /// How quickly the output grows, in volts per unit of error per second.
+    pub gain: f64,
+    /// The largest magnitude of voltage the controller outputs.
+    pub max_voltage: f64,
+    output: f64,
+    take_back: f64,
+    last_error: f64,
+}
+
+impl TakeBackHalf {
+    /// Creates a controller with the given gain, limited to [`Motor::MAX_VOLTAGE`].
+    pub const fn new(gain: f64) -> Self {
+        Self {
+            gain,
+            max_voltage: Motor::MAX_VOLTAGE,
+            output: 0.0,
+            take_back: 0.0,
+            last_error: 0.0,
+        }
+    }
+
+    /// Limits the magnitude of the output voltage.
+    pub const fn with_max_voltage(mut self, voltage: f64) -> Self {
+        self.max_voltage = voltage;
+        self
+    }
+}
+
+impl FlywheelController for TakeBackHalf {
+    fn calculate(&mut self, target: f64, velocity: f64, dt: Duration) -> f64 {
+        let error = target - velocity;
+
+        self.output = (self.output + self.gain * error * dt.as_secs_f64())
+            .clamp(-self.max_voltage, self.max_voltage);
+
+        if error * self.last_error < 0.0 {
+            self.output = (self.output + self.take_back) / 2.0;
+            self.take_back = self.output;
+        }
+        self.last_error = error;
+
+        self.output
+    }
+
+    fn reset(&mut self) {
+        self.output = 0.0;
+        self.take_back = 0.0;
+        self.last_error = 0.0;
+    }
+}
+
+/// A controller that predicts the voltage needed with a feedforward model and corrects the
+/// remaining error with a PID controller.
+///
+/// Uses this feedforward model: V = Kₛ sign(v) + Kᵥ v
+#[derive(Debug, Clone, Copy)]
+pub struct FeedforwardPid {
+    /// Voltage needed to overcome static friction.
+    pub ks: f64,
+    /// Voltage needed per unit of velocity.
+    pub kv: f64,
+    /// Corrects the error left over by the feedforward model.
+    pub pid: PidController,
+    /// The largest magnitude of voltage the controller outputs.
+    pub max_voltage: f64,
+}
+
+impl FeedforwardPid {
+    /// Creates a controller with the given feedforward gains and PID controller, limited to
+    /// [`Motor::MAX_VOLTAGE`].
+    pub const fn new(ks: f64, kv: f64, pid: PidController) -> Self {
+        Self {
+            ks,
+            kv,
+            pid,
+            max_voltage: Motor::MAX_VOLTAGE,
+        }
+    }
+
+    /// Limits the magnitude of the output voltage.
+    pub const fn with_max_voltage(mut self, voltage: f64) -> Self {
+        self.max_voltage = voltage;
+        self
+    }
+}
+
+impl FlywheelController for FeedforwardPid {
+    fn calculate(&mut self, target: f64, velocity: f64, dt: Duration) -> f64 {
+        let feedforward = self.ks * num::signum(target) + self.kv * target;
+        let feedback = self.pid.update_with_dt(target, velocity, dt);
+        (feedforward + feedback).clamp(-self.max_voltage, self.max_voltage)
+    }
+
+    fn reset(&mut self) {
+        self.pid.reset();
+    }
+}
+
+/// What a [`Flywheel`] is currently doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlywheelState {
+    /// The flywheel is settling at the target velocity, either because the target was changed
+    /// or because it left the tolerance without a shot being detected.
+    SpinningUp,
+    /// The flywheel has held the target velocity for the dwell time and is still within the
+    /// tolerance, so it is ready to shoot.
+    AtSpeed,
+    /// The flywheel was slowed down by a shot and is getting back up to speed.
+    Recovering,
+}
+
+/// A flywheel velocity controller that tracks whether the flywheel is ready to shoot.
+#[derive(Debug, Clone, Copy)]
+pub struct Flywheel<C> {
+    /// Chooses the voltage to apply to the flywheel.
+    pub controller: C,
+    /// The largest difference from the target velocity at which the flywheel is at speed.
+    pub tolerance: f64,
+    /// How long the velocity must stay within the tolerance before the flywheel is at speed.
+    pub dwell_time: Duration,
+    /// How far below the target velocity the flywheel must drop while at speed for a shot to
+    /// be detected.
+    pub shot_threshold: f64,
+
+    target: f64,
+    state: FlywheelState,
+    within_tolerance_for: Duration,
+    shots: u32,
+    recovery_started: Duration,
+    last_recovery_time: Option<Duration>,
+    elapsed: Duration,
+    last_time: Option<Instant>,
+}
+
+impl<C: FlywheelController> Flywheel<C> {
+    /// Creates a flywheel that is at speed once it has been within `tolerance` of the target
+    /// for `dwell_time`.
+    ///
+    /// Shots are detected when the velocity drops more than twice the tolerance below the
+    /// target by default.
+    pub fn new(controller: C, tolerance: f64, dwell_time: Duration) -> Self {
+        Self {
+            controller,
+            tolerance,
+            dwell_time,
+            shot_threshold: tolerance * 2.0,
+            target: 0.0,
+            state: FlywheelState::SpinningUp,
+            within_tolerance_for: Duration::ZERO,
+            shots: 0,
+            recovery_started: Duration::ZERO,
+            last_recovery_time: None,
+            elapsed: Duration::ZERO,
+            last_time: None,
+        }
+    }
+
+    /// Sets how far below the target velocity the flywheel must drop for a shot to be detected.
+    pub const fn with_shot_threshold(mut self, threshold: f64) -> Self {
+        self.shot_threshold = threshold;
+        self
+    }
+
+    /// Returns the target velocity.
+    pub const fn target(&self) -> f64 {
+        self.target
+    }
+
+    /// Sets the target velocity.
+    ///
+    /// Changing the target resets the controller and the flywheel goes back to spinning up.
+    pub fn set_target(&mut self, target: f64) {
+        if target != self.target {
+            self.target = target;
+            self.state = FlywheelState::SpinningUp;
+            self.within_tolerance_for = Duration::ZERO;
+            self.controller.reset();
+        }
+    }
+
+    /// Returns what the flywheel is currently doing.
+    pub const fn state(&self) -> FlywheelState {
+        self.state
+    }
+
+    /// Returns `true` if the flywheel is at speed and ready to shoot.
+    pub fn is_at_speed(&self) -> bool {
+        self.state == FlywheelState::AtSpeed
+    }
+
+    /// Returns the number of shots detected since the flywheel was created.
+    pub const fn shots(&self) -> u32 {
+        self.shots
+    }
+
+    /// Returns how long the flywheel took to get back to speed after the most recent shot it
+    /// has recovered from.
+    pub const fn last_recovery_time(&self) -> Option<Duration> {
+        self.last_recovery_time
+    }
+
+    /// Updates the flywheel with its measured velocity, using the time since the last update,
+    /// and returns the voltage to apply.
+    pub fn update(&mut self, velocity: f64) -> f64 {
+        let now = Instant::now();
+        let dt = self
+            .last_time
+            .map_or(Duration::ZERO, |last_time| now.duration_since(last_time));
+        self.last_time = Some(now);
+
+        self.update_with_dt(velocity, dt)
+    }
+
+    /// Updates the flywheel with its measured velocity, given the time since the last update,
+    /// and returns the voltage to apply.
+    pub fn update_with_dt(&mut self, velocity: f64, dt: Duration) -> f64 {
+        self.elapsed += dt;
+
+        let error = self.target - velocity;
+        // Velocities are compared by magnitude so that flywheels spinning in reverse are
+        // handled the same way.
+        let shortfall = if self.target < 0.0 { -error } else { error };
+
+        if error.abs() <= self.tolerance {
+            self.within_tolerance_for += dt;
+        } else {
+            self.within_tolerance_for = Duration::ZERO;
+        }
+        let settled = error.abs() <= self.tolerance && self.within_tolerance_for >= self.dwell_time;
+
+        self.state = match self.state {
+            FlywheelState::AtSpeed if shortfall > self.shot_threshold => {
+                self.shots += 1;
+                self.recovery_started = self.elapsed;
+                FlywheelState::Recovering
+            }
+            // Drifting out of tolerance without a shot, such as by overshooting, means the
+            // flywheel has to settle again before it is ready.
+            FlywheelState::AtSpeed if error.abs() > self.tolerance => FlywheelState::SpinningUp,
+            FlywheelState::Recovering if settled => {
+                self.last_recovery_time = Some(self.elapsed - self.recovery_started);
+                FlywheelState::AtSpeed
+            }
+            FlywheelState::SpinningUp if settled => FlywheelState::AtSpeed,
+            state => state,
+        };
+
+        self.controller.calculate(self.target, velocity, dt)
+    }
+}
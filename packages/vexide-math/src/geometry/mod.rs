@@ -0,0 +1,23 @@
+//! Planar geometry for robot localization.
+//!
+//! These types describe positions and movement on the field:
+//!
+//! - [`Vec2`] is a 2D vector, used for positions and displacements.
+//! - [`Rotation2d`] is an angle, used for headings.
+//! - [`Pose2d`] is a position and heading on the field.
+//! - [`Transform2d`] is a movement from one pose to another, relative to the first pose.
+//!
+//! Angles are measured in radians counterclockwise from the positive x axis. Vectors,
+//! rotations, poses and transforms convert to and from their [`mint`] equivalents, so they can
+//! be used with other math libraries and with device readings such as those from the
+//! [`InertialSensor`](vexide_devices::smart::InertialSensor).
+
+mod pose;
+mod rotation;
+mod transform;
+mod vec2;
+
+pub use pose::Pose2d;
+pub use rotation::Rotation2d;
+pub use transform::Transform2d;
+pub use vec2::Vec2;
@@ -0,0 +1,113 @@
+use core::ops::{Add, AddAssign, Sub};
+
+use super::{Rotation2d, Transform2d, Vec2};
+
+/// A position and heading on the field.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Pose2d {
+    /// The position.
+    pub position: Vec2,
+    /// The heading.
+    pub heading: Rotation2d,
+}
+
+impl Pose2d {
+    /// The origin, facing the positive x axis.
+    pub const ORIGIN: Self = Self::new(Vec2::ZERO, Rotation2d::ZERO);
+
+    /// Creates a new pose.
+    pub const fn new(position: Vec2, heading: Rotation2d) -> Self {
+        Self { position, heading }
+    }
+
+    /// Creates a new pose from coordinates and a heading in radians.
+    pub const fn from_xy_radians(x: f64, y: f64, heading: f64) -> Self {
+        Self::new(Vec2::new(x, y), Rotation2d::from_radians(heading))
+    }
+
+    /// Returns the x coordinate.
+    pub const fn x(&self) -> f64 {
+        self.position.x
+    }
+
+    /// Returns the y coordinate.
+    pub const fn y(&self) -> f64 {
+        self.position.y
+    }
+
+    /// Applies a transform relative to this pose.
+    pub fn transform_by(&self, transform: Transform2d) -> Self {
+        Self::new(
+            self.position + transform.translation.rotate_by(self.heading),
+            self.heading + transform.rotation,
+        )
+    }
+
+    /// Returns this pose as seen from `origin`, where `origin` is at (0, 0) facing the positive
+    /// x axis.
+    pub fn relative_to(&self, origin: Self) -> Self {
+        let transform = *self - origin;
+        Self::new(transform.translation, transform.rotation)
+    }
+
+    /// Converts a point from this pose's frame, where the pose faces the positive x axis, into
+    /// the field frame.
+    pub fn to_field(&self, point: Vec2) -> Vec2 {
+        self.position + point.rotate_by(self.heading)
+    }
+
+    /// Converts a point from the field frame into this pose's frame, where the pose faces the
+    /// positive x axis.
+    pub fn to_local(&self, point: Vec2) -> Vec2 {
+        (point - self.position).rotate_by(-self.heading)
+    }
+
+    /// Linearly interpolates the position, and interpolates the heading along the shortest
+    /// path, where `t = 0.0` gives `self` and `t = 1.0` gives `other`.
+    pub fn interpolate(&self, other: Self, t: f64) -> Self {
+        Self::new(
+            self.position.lerp(other.position, t),
+            self.heading.interpolate(other.heading, t),
+        )
+    }
+}
+
+impl Add<Transform2d> for Pose2d {
+    type Output = Self;
+
+    fn add(self, rhs: Transform2d) -> Self::Output {
+        self.transform_by(rhs)
+    }
+}
+
+impl AddAssign<Transform2d> for Pose2d {
+    fn add_assign(&mut self, rhs: Transform2d) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Pose2d {
+    type Output = Transform2d;
+
+    /// Returns the transform that moves `rhs` to `self`.
+    fn sub(self, rhs: Self) -> Self::Output {
+        Transform2d::between(rhs, self)
+    }
+}
+
+impl From<mint::ColumnMatrix3<f64>> for Pose2d {
+    /// Converts a homogeneous transformation matrix from a pose's frame to the field frame into
+    /// that pose, with a heading in the range `(-π, π]`.
+    fn from(matrix: mint::ColumnMatrix3<f64>) -> Self {
+        let transform = Transform2d::from(matrix);
+        Self::new(transform.translation, transform.rotation)
+    }
+}
+
+impl From<Pose2d> for mint::ColumnMatrix3<f64> {
+    /// Converts the pose into a homogeneous transformation matrix from the pose's frame to the
+    /// field frame.
+    fn from(pose: Pose2d) -> Self {
+        Transform2d::new(pose.position, pose.heading).into()
+    }
+}
@@ -0,0 +1,170 @@
+use core::{
+    f64::consts::PI,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
+/// A rotation in the plane, such as a robot's heading.
+///
+/// Rotations keep the angle they were created with, so they can represent more than one full
+/// turn. Use [`Rotation2d::wrapped`] to get the equivalent angle in the range `(-π, π]`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Rotation2d {
+    radians: f64,
+}
+
+impl Rotation2d {
+    /// No rotation.
+    pub const ZERO: Self = Self::from_radians(0.0);
+
+    /// Creates a rotation from an angle in radians, counterclockwise positive.
+    pub const fn from_radians(radians: f64) -> Self {
+        Self { radians }
+    }
+
+    /// Creates a rotation from an angle in degrees, counterclockwise positive.
+    pub fn from_degrees(degrees: f64) -> Self {
+        Self::from_radians(degrees.to_radians())
+    }
+
+    /// Creates a rotation from the yaw of a 3D orientation, which is its rotation about the
+    /// z axis, in the range `(-π, π]`.
+    ///
+    /// This can be used with the orientation reported by
+    /// [`InertialSensor::quaternion`](vexide_devices::smart::InertialSensor::quaternion).
+    pub fn from_quaternion(quaternion: mint::Quaternion<f64>) -> Self {
+        let mint::Quaternion { v, s: w } = quaternion;
+        let sin_yaw = 2.0 * (w * v.z + v.x * v.y);
+        let cos_yaw = 1.0 - 2.0 * (v.y * v.y + v.z * v.z);
+        Self::from_radians(sin_yaw.atan2(cos_yaw))
+    }
+
+    /// Creates a rotation from a compass-style heading in degrees, which increases clockwise
+    /// from the positive y axis.
+    ///
+    /// This is the convention used by [`InertialSensor::heading`](vexide_devices::smart::InertialSensor::heading).
+    pub fn from_compass_degrees(degrees: f64) -> Self {
+        Self::from_degrees(90.0 - degrees)
+    }
+
+    /// Returns the angle in radians.
+    pub const fn radians(&self) -> f64 {
+        self.radians
+    }
+
+    /// Returns the angle in degrees.
+    pub fn degrees(&self) -> f64 {
+        self.radians.to_degrees()
+    }
+
+    /// Returns the cosine of the angle.
+    pub fn cos(&self) -> f64 {
+        self.radians.cos()
+    }
+
+    /// Returns the sine of the angle.
+    pub fn sin(&self) -> f64 {
+        self.radians.sin()
+    }
+
+    /// Returns the tangent of the angle.
+    pub fn tan(&self) -> f64 {
+        self.radians.tan()
+    }
+
+    /// Returns the equivalent rotation in the range `(-π, π]`.
+    pub fn wrapped(&self) -> Self {
+        let wrapped = self.radians - 2.0 * PI * ((self.radians + PI) / (2.0 * PI)).floor();
+        Self::from_radians(if wrapped == -PI { PI } else { wrapped })
+    }
+
+    /// Returns the smallest rotation that turns `self` to `other`, in the range `(-π, π]`.
+    pub fn shortest_difference(&self, other: Self) -> Self {
+        (other - *self).wrapped()
+    }
+
+    /// Returns the inverse rotation, which undoes this rotation.
+    pub fn inverse(&self) -> Self {
+        Self::from_radians(-self.radians)
+    }
+
+    /// Interpolates between two rotations along the shortest path, where `t = 0.0` gives
+    /// `self` and `t = 1.0` gives a rotation equivalent to `other`.
+    pub fn interpolate(&self, other: Self, t: f64) -> Self {
+        *self + self.shortest_difference(other) * t
+    }
+}
+
+impl Add for Rotation2d {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_radians(self.radians + rhs.radians)
+    }
+}
+
+impl AddAssign for Rotation2d {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Rotation2d {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_radians(self.radians - rhs.radians)
+    }
+}
+
+impl SubAssign for Rotation2d {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<f64> for Rotation2d {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self::from_radians(self.radians * rhs)
+    }
+}
+
+impl Neg for Rotation2d {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.inverse()
+    }
+}
+
+impl From<mint::ColumnMatrix2<f64>> for Rotation2d {
+    /// Converts a rotation matrix into a rotation in the range `(-π, π]`.
+    fn from(matrix: mint::ColumnMatrix2<f64>) -> Self {
+        Self::from_radians(matrix.x.y.atan2(matrix.x.x))
+    }
+}
+
+impl From<mint::Quaternion<f64>> for Rotation2d {
+    /// Extracts the yaw of a 3D orientation. See [`Rotation2d::from_quaternion`].
+    fn from(quaternion: mint::Quaternion<f64>) -> Self {
+        Self::from_quaternion(quaternion)
+    }
+}
+
+impl From<Rotation2d> for mint::ColumnMatrix2<f64> {
+    fn from(rotation: Rotation2d) -> Self {
+        let (sin, cos) = (rotation.sin(), rotation.cos());
+        Self {
+            x: mint::Vector2 { x: cos, y: sin },
+            y: mint::Vector2 { x: -sin, y: cos },
+        }
+    }
+}
+
+impl mint::IntoMint for Rotation2d {
+    type MintType = mint::ColumnMatrix2<f64>;
+}
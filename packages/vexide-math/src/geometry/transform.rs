@@ -0,0 +1,90 @@
+use core::ops::Mul;
+
+use super::{Pose2d, Rotation2d, Vec2};
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
+/// A movement from one pose to another, measured in the frame of the starting pose.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Transform2d {
+    /// The change in position, relative to the starting pose's heading.
+    pub translation: Vec2,
+    /// The change in heading.
+    pub rotation: Rotation2d,
+}
+
+impl Transform2d {
+    /// The transform that doesn't move anything.
+    pub const IDENTITY: Self = Self::new(Vec2::ZERO, Rotation2d::ZERO);
+
+    /// Creates a new transform.
+    pub const fn new(translation: Vec2, rotation: Rotation2d) -> Self {
+        Self {
+            translation,
+            rotation,
+        }
+    }
+
+    /// Returns the transform that moves `initial` to `last`.
+    pub fn between(initial: Pose2d, last: Pose2d) -> Self {
+        Self::new(
+            (last.position - initial.position).rotate_by(-initial.heading),
+            last.heading - initial.heading,
+        )
+    }
+
+    /// Returns the transform that undoes this one.
+    pub fn inverse(&self) -> Self {
+        Self::new(
+            (-self.translation).rotate_by(-self.rotation),
+            -self.rotation,
+        )
+    }
+}
+
+impl Mul for Transform2d {
+    type Output = Self;
+
+    /// Composes two transforms, applying `self` and then `rhs`.
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.translation + rhs.translation.rotate_by(self.rotation),
+            self.rotation + rhs.rotation,
+        )
+    }
+}
+
+impl From<mint::ColumnMatrix3<f64>> for Transform2d {
+    /// Converts a homogeneous transformation matrix into a transform with a rotation in the
+    /// range `(-π, π]`.
+    fn from(matrix: mint::ColumnMatrix3<f64>) -> Self {
+        Self::new(
+            Vec2::new(matrix.z.x, matrix.z.y),
+            Rotation2d::from_radians(matrix.x.y.atan2(matrix.x.x)),
+        )
+    }
+}
+
+impl From<Transform2d> for mint::ColumnMatrix3<f64> {
+    /// Converts the transform into a homogeneous transformation matrix.
+    fn from(transform: Transform2d) -> Self {
+        let (sin, cos) = (transform.rotation.sin(), transform.rotation.cos());
+        Self {
+            x: mint::Vector3 {
+                x: cos,
+                y: sin,
+                z: 0.0,
+            },
+            y: mint::Vector3 {
+                x: -sin,
+                y: cos,
+                z: 0.0,
+            },
+            z: mint::Vector3 {
+                x: transform.translation.x,
+                y: transform.translation.y,
+                z: 1.0,
+            },
+        }
+    }
+}
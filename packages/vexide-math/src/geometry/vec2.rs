@@ -0,0 +1,202 @@
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::Rotation2d;
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
+/// A two-dimensional vector.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec2 {
+    /// The x component.
+    pub x: f64,
+    /// The y component.
+    pub y: f64,
+}
+
+impl Vec2 {
+    /// The zero vector.
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+
+    /// Creates a new vector.
+    pub const fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// Creates a vector with the given length pointing in the direction of `angle`.
+    pub fn from_polar(length: f64, angle: Rotation2d) -> Self {
+        Self::new(length * angle.cos(), length * angle.sin())
+    }
+
+    /// Returns the length of the vector.
+    pub fn length(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// Returns the squared length of the vector, which is cheaper to calculate than the length.
+    pub fn length_squared(&self) -> f64 {
+        self.dot(*self)
+    }
+
+    /// Returns the distance between two points.
+    pub fn distance(&self, other: Self) -> f64 {
+        (other - *self).length()
+    }
+
+    /// Returns the dot product of two vectors.
+    pub fn dot(&self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns the z component of the cross product of two vectors.
+    ///
+    /// This is positive when `other` is counterclockwise from `self`.
+    pub fn cross(&self, other: Self) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns a vector with the same direction and a length of 1, or the zero vector if this
+    /// vector has no length.
+    pub fn normalized(&self) -> Self {
+        let length = self.length();
+        if length == 0.0 {
+            Self::ZERO
+        } else {
+            *self / length
+        }
+    }
+
+    /// Returns the direction the vector points in.
+    pub fn angle(&self) -> Rotation2d {
+        Rotation2d::from_radians(self.y.atan2(self.x))
+    }
+
+    /// Rotates the vector counterclockwise about the origin.
+    pub fn rotate_by(&self, rotation: Rotation2d) -> Self {
+        let (sin, cos) = (rotation.sin(), rotation.cos());
+        Self::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// Linearly interpolates between two vectors, where `t = 0.0` gives `self` and `t = 1.0`
+    /// gives `other`.
+    pub fn lerp(&self, other: Self, t: f64) -> Self {
+        *self + (other - *self) * t
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<f64> for Vec2 {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Mul<Vec2> for f64 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: Vec2) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl MulAssign<f64> for Vec2 {
+    fn mul_assign(&mut self, rhs: f64) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div<f64> for Vec2 {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Self::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl DivAssign<f64> for Vec2 {
+    fn div_assign(&mut self, rhs: f64) {
+        *self = *self / rhs;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl From<(f64, f64)> for Vec2 {
+    fn from((x, y): (f64, f64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<[f64; 2]> for Vec2 {
+    fn from([x, y]: [f64; 2]) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<Vec2> for [f64; 2] {
+    fn from(vec: Vec2) -> Self {
+        [vec.x, vec.y]
+    }
+}
+
+impl From<mint::Vector2<f64>> for Vec2 {
+    fn from(vec: mint::Vector2<f64>) -> Self {
+        Self::new(vec.x, vec.y)
+    }
+}
+
+impl From<Vec2> for mint::Vector2<f64> {
+    fn from(vec: Vec2) -> Self {
+        Self { x: vec.x, y: vec.y }
+    }
+}
+
+impl From<mint::Point2<f64>> for Vec2 {
+    fn from(point: mint::Point2<f64>) -> Self {
+        Self::new(point.x, point.y)
+    }
+}
+
+impl From<Vec2> for mint::Point2<f64> {
+    fn from(vec: Vec2) -> Self {
+        Self { x: vec.x, y: vec.y }
+    }
+}
+
+impl mint::IntoMint for Vec2 {
+    type MintType = mint::Vector2<f64>;
+}
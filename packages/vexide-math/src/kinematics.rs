@@ -0,0 +1,401 @@
+//! Drivetrain kinematics.
+//!
+//! Kinematics convert between the velocity of the whole robot, described by [`ChassisSpeeds`],
+//! and the speeds of its individual wheels. Inverse kinematics
+//! ([`Kinematics::to_wheel_speeds`]) give the wheel speeds that make the robot move at a desired
+//! velocity, while forward kinematics ([`Kinematics::to_chassis_speeds`]) give the velocity of
+//! the robot from measured wheel speeds.
+//!
+//! Kinematics are provided for [differential](DifferentialKinematics) (tank),
+//! [X-drive](XDriveKinematics), [mecanum](MecanumKinematics) and [H-drive](HDriveKinematics)
+//! drivetrains.
+//!
+//! Wheel speeds are measured in the same units as the chassis speeds. They can be converted to
+//! RPM with [`WheelSpeeds::linear_to_rpm`] and then limited to what the motors can reach with
+//! [`WheelSpeeds::desaturate`], for example:
+//!
+//! ```no_run
+//! use vexide_devices::smart::motor::Gearset;
+//! use vexide_math::kinematics::{ChassisSpeeds, Kinematics, MecanumKinematics, WheelSpeeds};
+//!
+//! let kinematics = MecanumKinematics::new(12.0, 10.0);
+//! let wheel_rpm = kinematics
+//!     .to_wheel_speeds(ChassisSpeeds::new(40.0, 20.0, 1.0))
+//!     .linear_to_rpm(4.0)
+//!     .desaturate(Gearset::Green.max_rpm());
+//! ```
+
+use core::f64::consts::{PI, SQRT_2};
+
+use crate::geometry::{Rotation2d, Vec2};
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
+/// The velocity of a robot in its own frame of reference.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ChassisSpeeds {
+    /// The forward velocity.
+    pub vx: f64,
+    /// The sideways velocity, positive to the left.
+    pub vy: f64,
+    /// The rate of rotation in radians per second, counterclockwise positive.
+    pub omega: f64,
+}
+
+impl ChassisSpeeds {
+    /// A robot at rest.
+    pub const ZERO: Self = Self::new(0.0, 0.0, 0.0);
+
+    /// Creates chassis speeds from velocities relative to the robot.
+    pub const fn new(vx: f64, vy: f64, omega: f64) -> Self {
+        Self { vx, vy, omega }
+    }
+
+    /// Creates chassis speeds from velocities relative to the field, given the robot's current
+    /// heading.
+    ///
+    /// This is used for field-relative driving, where pushing a joystick forward moves the robot
+    /// away from the driver no matter which way it is facing.
+    pub fn from_field_relative(vx: f64, vy: f64, omega: f64, heading: Rotation2d) -> Self {
+        let velocity = Vec2::new(vx, vy).rotate_by(-heading);
+        Self::new(velocity.x, velocity.y, omega)
+    }
+
+    /// Returns the velocities relative to the field, given the robot's current heading.
+    pub fn to_field_relative(self, heading: Rotation2d) -> Self {
+        let velocity = Vec2::new(self.vx, self.vy).rotate_by(heading);
+        Self::new(velocity.x, velocity.y, self.omega)
+    }
+}
+
+/// Conversions between chassis speeds and the wheel speeds of a drivetrain.
+pub trait Kinematics {
+    /// The speeds of each of the drivetrain's wheels.
+    type WheelSpeeds: WheelSpeeds;
+
+    /// Calculates the wheel speeds that make the robot move at `speeds`.
+    fn to_wheel_speeds(&self, speeds: ChassisSpeeds) -> Self::WheelSpeeds;
+
+    /// Calculates how the robot is moving from the speeds of its wheels.
+    fn to_chassis_speeds(&self, wheel_speeds: Self::WheelSpeeds) -> ChassisSpeeds;
+}
+
+/// The speeds of each wheel of a drivetrain.
+pub trait WheelSpeeds: Sized {
+    /// Applies `f` to the speed of every wheel.
+    #[must_use]
+    fn map(self, f: impl FnMut(f64) -> f64) -> Self;
+
+    /// Returns the largest magnitude of any wheel's speed.
+    fn max_magnitude(&self) -> f64;
+
+    /// Slows every wheel down by the same ratio so that none exceeds `max_speed`.
+    ///
+    /// Scaling all wheels equally keeps the robot moving in the same direction and turning
+    /// about the same point, only slower. Speeds that are already within the limit are returned
+    /// unchanged.
+    #[must_use]
+    fn desaturate(self, max_speed: f64) -> Self {
+        let fastest = self.max_magnitude();
+        if fastest > max_speed {
+            let scale = max_speed / fastest;
+            self.map(|speed| speed * scale)
+        } else {
+            self
+        }
+    }
+
+    /// Converts linear wheel speeds, measured in units per second, to RPM for wheels with the
+    /// given diameter.
+    #[must_use]
+    fn linear_to_rpm(self, wheel_diameter: f64) -> Self {
+        self.map(|speed| speed * 60.0 / (PI * wheel_diameter))
+    }
+
+    /// Converts wheel speeds in RPM to linear speeds, measured in units per second, for wheels
+    /// with the given diameter.
+    #[must_use]
+    fn rpm_to_linear(self, wheel_diameter: f64) -> Self {
+        self.map(|rpm| rpm * PI * wheel_diameter / 60.0)
+    }
+}
+
+/// The speeds of the left and right sides of a differential drivetrain.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DifferentialWheelSpeeds {
+    /// The speed of the left side, positive when driving forwards.
+    pub left: f64,
+    /// The speed of the right side, positive when driving forwards.
+    pub right: f64,
+}
+
+impl WheelSpeeds for DifferentialWheelSpeeds {
+    fn map(self, mut f: impl FnMut(f64) -> f64) -> Self {
+        Self {
+            left: f(self.left),
+            right: f(self.right),
+        }
+    }
+
+    fn max_magnitude(&self) -> f64 {
+        self.left.abs().max(self.right.abs())
+    }
+}
+
+/// Kinematics for a differential (tank) drivetrain, which can only drive forwards and turn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifferentialKinematics {
+    /// The distance between the left and right wheels.
+    pub track_width: f64,
+}
+
+impl DifferentialKinematics {
+    /// Creates kinematics for a drivetrain with the given distance between its left and right
+    /// wheels.
+    pub const fn new(track_width: f64) -> Self {
+        Self { track_width }
+    }
+}
+
+impl Kinematics for DifferentialKinematics {
+    type WheelSpeeds = DifferentialWheelSpeeds;
+
+    /// Calculates the wheel speeds that make the robot move at `speeds`.
+    ///
+    /// Differential drivetrains can't move sideways, so `speeds.vy` is ignored.
+    fn to_wheel_speeds(&self, speeds: ChassisSpeeds) -> Self::WheelSpeeds {
+        let turn = speeds.omega * self.track_width / 2.0;
+        DifferentialWheelSpeeds {
+            left: speeds.vx - turn,
+            right: speeds.vx + turn,
+        }
+    }
+
+    fn to_chassis_speeds(&self, wheel_speeds: Self::WheelSpeeds) -> ChassisSpeeds {
+        ChassisSpeeds::new(
+            (wheel_speeds.left + wheel_speeds.right) / 2.0,
+            0.0,
+            (wheel_speeds.right - wheel_speeds.left) / self.track_width,
+        )
+    }
+}
+
+/// The speeds of each wheel of a four-wheeled holonomic drivetrain.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HolonomicWheelSpeeds {
+    /// The speed of the front left wheel, positive when driving forwards.
+    pub front_left: f64,
+    /// The speed of the front right wheel, positive when driving forwards.
+    pub front_right: f64,
+    /// The speed of the back left wheel, positive when driving forwards.
+    pub back_left: f64,
+    /// The speed of the back right wheel, positive when driving forwards.
+    pub back_right: f64,
+}
+
+impl WheelSpeeds for HolonomicWheelSpeeds {
+    fn map(self, mut f: impl FnMut(f64) -> f64) -> Self {
+        Self {
+            front_left: f(self.front_left),
+            front_right: f(self.front_right),
+            back_left: f(self.back_left),
+            back_right: f(self.back_right),
+        }
+    }
+
+    fn max_magnitude(&self) -> f64 {
+        self.front_left
+            .abs()
+            .max(self.front_right.abs())
+            .max(self.back_left.abs())
+            .max(self.back_right.abs())
+    }
+}
+
+/// Kinematics for a mecanum drivetrain.
+///
+/// The rollers of each wheel are assumed to be angled at 45° so that, viewed from above, they
+/// form an X shape across the robot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MecanumKinematics {
+    /// The distance between the left and right wheels.
+    pub track_width: f64,
+    /// The distance between the front and back wheels.
+    pub wheelbase: f64,
+}
+
+impl MecanumKinematics {
+    /// Creates kinematics for a drivetrain with the given distances between its left and right
+    /// wheels and its front and back wheels.
+    pub const fn new(track_width: f64, wheelbase: f64) -> Self {
+        Self {
+            track_width,
+            wheelbase,
+        }
+    }
+
+    /// The distance each wheel travels per radian the robot turns, measured along the wheel's
+    /// direction of travel.
+    fn turn_radius(&self) -> f64 {
+        (self.track_width + self.wheelbase) / 2.0
+    }
+}
+
+impl Kinematics for MecanumKinematics {
+    type WheelSpeeds = HolonomicWheelSpeeds;
+
+    fn to_wheel_speeds(&self, speeds: ChassisSpeeds) -> Self::WheelSpeeds {
+        let turn = speeds.omega * self.turn_radius();
+        HolonomicWheelSpeeds {
+            front_left: speeds.vx - speeds.vy - turn,
+            front_right: speeds.vx + speeds.vy + turn,
+            back_left: speeds.vx + speeds.vy - turn,
+            back_right: speeds.vx - speeds.vy + turn,
+        }
+    }
+
+    fn to_chassis_speeds(&self, wheel_speeds: Self::WheelSpeeds) -> ChassisSpeeds {
+        let HolonomicWheelSpeeds {
+            front_left,
+            front_right,
+            back_left,
+            back_right,
+        } = wheel_speeds;
+
+        ChassisSpeeds::new(
+            (front_left + front_right + back_left + back_right) / 4.0,
+            (-front_left + front_right + back_left - back_right) / 4.0,
+            (-front_left + front_right - back_left + back_right) / (4.0 * self.turn_radius()),
+        )
+    }
+}
+
+/// Kinematics for an X-drive, a holonomic drivetrain with omni wheels mounted at 45° on each
+/// corner of the robot.
+///
+/// Each wheel drives diagonally, so driving forwards at a given speed requires each wheel to
+/// spin `1 / √2` as fast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XDriveKinematics {
+    /// The distance between the left and right wheels.
+    pub track_width: f64,
+    /// The distance between the front and back wheels.
+    pub wheelbase: f64,
+}
+
+impl XDriveKinematics {
+    /// Creates kinematics for a drivetrain with the given distances between its left and right
+    /// wheels and its front and back wheels.
+    pub const fn new(track_width: f64, wheelbase: f64) -> Self {
+        Self {
+            track_width,
+            wheelbase,
+        }
+    }
+
+    /// An X-drive moves like a mecanum drivetrain, except that its wheels only travel along
+    /// their diagonal.
+    const fn as_mecanum(&self) -> MecanumKinematics {
+        MecanumKinematics::new(self.track_width, self.wheelbase)
+    }
+}
+
+impl Kinematics for XDriveKinematics {
+    type WheelSpeeds = HolonomicWheelSpeeds;
+
+    fn to_wheel_speeds(&self, speeds: ChassisSpeeds) -> Self::WheelSpeeds {
+        self.as_mecanum()
+            .to_wheel_speeds(speeds)
+            .map(|speed| speed / SQRT_2)
+    }
+
+    fn to_chassis_speeds(&self, wheel_speeds: Self::WheelSpeeds) -> ChassisSpeeds {
+        self.as_mecanum()
+            .to_chassis_speeds(wheel_speeds.map(|speed| speed * SQRT_2))
+    }
+}
+
+/// The speeds of the wheels of an H-drive.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HDriveWheelSpeeds {
+    /// The speed of the left side, positive when driving forwards.
+    pub left: f64,
+    /// The speed of the right side, positive when driving forwards.
+    pub right: f64,
+    /// The speed of the sideways wheel, positive when driving to the left.
+    pub center: f64,
+}
+
+impl WheelSpeeds for HDriveWheelSpeeds {
+    fn map(self, mut f: impl FnMut(f64) -> f64) -> Self {
+        Self {
+            left: f(self.left),
+            right: f(self.right),
+            center: f(self.center),
+        }
+    }
+
+    fn max_magnitude(&self) -> f64 {
+        self.left.abs().max(self.right.abs()).max(self.center.abs())
+    }
+}
+
+/// Kinematics for an H-drive, a differential drivetrain with an extra wheel mounted sideways for
+/// strafing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HDriveKinematics {
+    /// The distance between the left and right wheels.
+    pub track_width: f64,
+    /// The distance of the sideways wheel in front of the robot's center of rotation. Negative
+    /// values are behind the center.
+    pub center_offset: f64,
+}
+
+impl HDriveKinematics {
+    /// Creates kinematics for a drivetrain with the given distance between its left and right
+    /// wheels, with the sideways wheel at its center of rotation.
+    pub const fn new(track_width: f64) -> Self {
+        Self {
+            track_width,
+            center_offset: 0.0,
+        }
+    }
+
+    /// Sets the distance of the sideways wheel in front of the robot's center of rotation.
+    pub const fn with_center_offset(mut self, offset: f64) -> Self {
+        self.center_offset = offset;
+        self
+    }
+
+    const fn as_differential(&self) -> DifferentialKinematics {
+        DifferentialKinematics::new(self.track_width)
+    }
+}
+
+impl Kinematics for HDriveKinematics {
+    type WheelSpeeds = HDriveWheelSpeeds;
+
+    fn to_wheel_speeds(&self, speeds: ChassisSpeeds) -> Self::WheelSpeeds {
+        let DifferentialWheelSpeeds { left, right } =
+            self.as_differential().to_wheel_speeds(speeds);
+
+        // A wheel in front of the center of rotation is pushed to the left as the robot turns
+        // counterclockwise.
+        HDriveWheelSpeeds {
+            left,
+            right,
+            center: speeds.vy + speeds.omega * self.center_offset,
+        }
+    }
+
+    fn to_chassis_speeds(&self, wheel_speeds: Self::WheelSpeeds) -> ChassisSpeeds {
+        let mut speeds = self
+            .as_differential()
+            .to_chassis_speeds(DifferentialWheelSpeeds {
+                left: wheel_speeds.left,
+                right: wheel_speeds.right,
+            });
+        speeds.vy = wheel_speeds.center - speeds.omega * self.center_offset;
+        speeds
+    }
+}
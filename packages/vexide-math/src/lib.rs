@@ -1,6 +1,33 @@
 //! Common mathematical formulas and models implemented for [`vexide`](https://crates.io/crates/vexide).
 
 #![no_std]
+#![feature(error_in_core)]
 
+extern crate alloc;
+
+/// Items glob-imported by every module that does floating point math.
+///
+/// Float methods such as `sqrt` and `sin_cos` are inherent methods provided by `std`, which
+/// isn't linked on the brain or in the WASM simulator. There, [`num::Float`] provides them
+/// through `libm` instead, so the prelude and the imports of it only exist on those targets.
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+mod prelude {
+    pub(crate) use num::Float;
+}
+
+pub mod autotune;
+pub mod estimator;
 pub mod feedforward;
+pub mod filter;
+pub mod flywheel;
+pub mod geometry;
+pub mod kinematics;
+pub mod motion;
+pub mod odometry;
 pub mod pid;
+pub mod profile;
+pub mod pursuit;
+pub mod ramsete;
+pub mod spline;
+pub mod sysid;
+pub mod trajectory;
@@ -0,0 +1,399 @@
+//! Autonomous motion controllers for differential drivetrains.
+//!
+//! These controllers drive the robot to a target using its current pose, such as from
+//! [`Odometry`](crate::odometry::Odometry):
+//!
+//! - [`TurnToHeading`] turns in place to face a heading.
+//! - [`DriveDistance`] drives straight forwards or backwards a set distance.
+//! - [`Boomerang`] drives to a position and arrives facing a heading, by chasing a "carrot"
+//!   point that leads the robot onto the target heading.
+//!
+//! Each controller is updated with the robot's pose until [`is_finished`](Boomerang::is_finished)
+//! returns `true`, either because it has settled at the target or because it timed out. Updates
+//! return the speeds to drive each side of the drivetrain at, in the same units as the outputs
+//! of the controller's [`PidController`]s, such as volts for [`Motor::set_voltage`].
+//!
+//! [`Motor::set_voltage`]: vexide_devices::smart::Motor::set_voltage
+
+use core::{f64::consts::PI, time::Duration};
+
+use vexide_core::time::Instant;
+
+use crate::{
+    geometry::{Pose2d, Rotation2d, Vec2},
+    kinematics::{DifferentialWheelSpeeds, WheelSpeeds},
+    pid::PidController,
+};
+
+/// Returns the time since `last_time`, and updates it to now.
+fn time_since(last_time: &mut Option<Instant>) -> Duration {
+    let now = Instant::now();
+    let dt = last_time.map_or(Duration::ZERO, |last_time| now.duration_since(last_time));
+    *last_time = Some(now);
+    dt
+}
+
+/// Runs the PID controller on an error calculated elsewhere.
+///
+/// The error is passed as the negated measurement, so the derivative is taken on the error.
+fn update_on_error(pid: &mut PidController, error: f64, dt: Duration) -> f64 {
+    pid.update_with_dt(0.0, -error, dt)
+}
+
+/// Speeds for turning in place, counterclockwise positive.
+fn turn_speeds(turn: f64) -> DifferentialWheelSpeeds {
+    DifferentialWheelSpeeds {
+        left: -turn,
+        right: turn,
+    }
+}
+
+/// Turns the robot in place to face a heading.
+#[derive(Debug, Clone, Copy)]
+pub struct TurnToHeading {
+    /// The heading to face.
+    pub target: Rotation2d,
+    /// Controls the turning speed from the error in heading, in radians.
+    pub pid: PidController,
+    /// How long the turn can take before it is abandoned.
+    pub timeout: Duration,
+    elapsed: Duration,
+    last_time: Option<Instant>,
+}
+
+impl TurnToHeading {
+    /// Creates a controller that turns to face `target`.
+    ///
+    /// The turn finishes once `pid` is settled. There is no timeout by default.
+    pub const fn new(target: Rotation2d, pid: PidController) -> Self {
+        Self {
+            target,
+            pid,
+            timeout: Duration::MAX,
+            elapsed: Duration::ZERO,
+            last_time: None,
+        }
+    }
+
+    /// Sets how long the turn can take before it is abandoned.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns `true` if the robot has settled facing the target heading.
+    pub fn is_settled(&self) -> bool {
+        self.pid.is_settled()
+    }
+
+    /// Returns `true` if the turn has taken longer than its timeout.
+    pub fn is_timed_out(&self) -> bool {
+        self.elapsed >= self.timeout
+    }
+
+    /// Returns `true` once the turn has settled or timed out.
+    pub fn is_finished(&self) -> bool {
+        self.is_settled() || self.is_timed_out()
+    }
+
+    /// Calculates the wheel speeds to turn towards the target from the robot's current heading,
+    /// using the time since the last update.
+    pub fn update(&mut self, heading: Rotation2d) -> DifferentialWheelSpeeds {
+        let dt = time_since(&mut self.last_time);
+        self.update_with_dt(heading, dt)
+    }
+
+    /// Calculates the wheel speeds to turn towards the target from the robot's current heading,
+    /// given the time since the last update.
+    ///
+    /// Once the turn is finished, both speeds are zero.
+    pub fn update_with_dt(&mut self, heading: Rotation2d, dt: Duration) -> DifferentialWheelSpeeds {
+        self.elapsed += dt;
+        if self.is_finished() {
+            return DifferentialWheelSpeeds::default();
+        }
+
+        // The robot always turns the shortest way round. Measuring the unwrapped heading keeps
+        // the derivative smooth as the heading wraps around.
+        let error = heading.shortest_difference(self.target).radians();
+        let measurement = heading.radians();
+        turn_speeds(
+            self.pid
+                .update_with_dt(measurement + error, measurement, dt),
+        )
+    }
+}
+
+/// Drives the robot straight forwards or backwards a set distance.
+#[derive(Debug, Clone, Copy)]
+pub struct DriveDistance {
+    /// The distance to drive, negative to drive backwards.
+    pub distance: f64,
+    /// Controls the driving speed from the remaining distance.
+    pub pid: PidController,
+    /// Keeps the robot facing its starting heading, controlling the turning speed from the
+    /// error in heading in radians.
+    pub heading_pid: Option<PidController>,
+    /// How long the drive can take before it is abandoned.
+    pub timeout: Duration,
+    start: Option<Pose2d>,
+    elapsed: Duration,
+    last_time: Option<Instant>,
+}
+
+impl DriveDistance {
+    /// Creates a controller that drives `distance` from wherever the robot is on its first
+    /// update.
+    ///
+    /// The drive finishes once `pid` is settled. There is no heading correction or timeout by
+    /// default.
+    pub const fn new(distance: f64, pid: PidController) -> Self {
+        Self {
+            distance,
+            pid,
+            heading_pid: None,
+            timeout: Duration::MAX,
+            start: None,
+            elapsed: Duration::ZERO,
+            last_time: None,
+        }
+    }
+
+    /// Corrects the robot's heading while driving to keep it facing the way it started.
+    pub const fn with_heading_correction(mut self, pid: PidController) -> Self {
+        self.heading_pid = Some(pid);
+        self
+    }
+
+    /// Sets how long the drive can take before it is abandoned.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns `true` if the robot has settled at the target distance.
+    pub fn is_settled(&self) -> bool {
+        self.pid.is_settled()
+    }
+
+    /// Returns `true` if the drive has taken longer than its timeout.
+    pub fn is_timed_out(&self) -> bool {
+        self.elapsed >= self.timeout
+    }
+
+    /// Returns `true` once the drive has settled or timed out.
+    pub fn is_finished(&self) -> bool {
+        self.is_settled() || self.is_timed_out()
+    }
+
+    /// Calculates the wheel speeds to drive towards the target distance from the robot's
+    /// current pose, using the time since the last update.
+    pub fn update(&mut self, pose: Pose2d) -> DifferentialWheelSpeeds {
+        let dt = time_since(&mut self.last_time);
+        self.update_with_dt(pose, dt)
+    }
+
+    /// Calculates the wheel speeds to drive towards the target distance from the robot's
+    /// current pose, given the time since the last update.
+    ///
+    /// Once the drive is finished, both speeds are zero.
+    pub fn update_with_dt(&mut self, pose: Pose2d, dt: Duration) -> DifferentialWheelSpeeds {
+        self.elapsed += dt;
+        if self.is_finished() {
+            return DifferentialWheelSpeeds::default();
+        }
+
+        let start = *self.start.get_or_insert(pose);
+
+        // Only movement along the starting heading counts towards the distance.
+        let travelled = (pose.position - start.position).dot(Vec2::from_polar(1.0, start.heading));
+        let drive = self.pid.update_with_dt(self.distance, travelled, dt);
+
+        let turn = self.heading_pid.as_mut().map_or(0.0, |heading_pid| {
+            let error = pose.heading.shortest_difference(start.heading).radians();
+            let measurement = pose.heading.radians();
+            heading_pid.update_with_dt(measurement + error, measurement, dt)
+        });
+
+        DifferentialWheelSpeeds {
+            left: drive - turn,
+            right: drive + turn,
+        }
+    }
+}
+
+/// Drives the robot to a position, arriving facing a heading.
+///
+/// Rather than driving straight at the target, the robot drives towards a carrot point behind
+/// the target along its heading. The carrot moves towards the target as the robot gets closer,
+/// so the robot curves in to arrive facing the right way. Once the robot is within
+/// [`Boomerang::close_distance`] of the target, it stops chasing the carrot and only corrects
+/// its distance along its heading and the final heading.
+#[derive(Debug, Clone, Copy)]
+pub struct Boomerang {
+    /// The pose to drive to.
+    pub target: Pose2d,
+    /// How far behind the target the carrot starts, as a fraction of the distance to the
+    /// target, in the range `[0.0, 1.0)`.
+    ///
+    /// Higher values make the robot swing out wider to approach the target head-on. A lead of
+    /// `0.0` drives straight to the target position. Defaults to `0.6`.
+    pub lead: f64,
+    /// The distance from the target at which the robot stops chasing the carrot.
+    pub close_distance: f64,
+    /// Controls the driving speed from the distance to the carrot.
+    pub linear_pid: PidController,
+    /// Controls the turning speed from the error in heading, in radians.
+    pub angular_pid: PidController,
+    /// The largest speed either side of the drivetrain is driven at. There is no limit by
+    /// default.
+    pub max_speed: f64,
+    /// Whether the robot drives to the target backwards.
+    pub reversed: bool,
+    /// How long the move can take before it is abandoned.
+    pub timeout: Duration,
+    elapsed: Duration,
+    last_time: Option<Instant>,
+}
+
+impl Boomerang {
+    /// Creates a controller that drives to `target`.
+    ///
+    /// The move finishes once both PID controllers are settled within `close_distance` of the
+    /// target. There is no speed limit or timeout by default.
+    pub const fn new(
+        target: Pose2d,
+        close_distance: f64,
+        linear_pid: PidController,
+        angular_pid: PidController,
+    ) -> Self {
+        Self {
+            target,
+            lead: 0.6,
+            close_distance,
+            linear_pid,
+            angular_pid,
+            max_speed: f64::INFINITY,
+            reversed: false,
+            timeout: Duration::MAX,
+            elapsed: Duration::ZERO,
+            last_time: None,
+        }
+    }
+
+    /// Sets how far behind the target the carrot starts, as a fraction of the distance to the
+    /// target.
+    pub const fn with_lead(mut self, lead: f64) -> Self {
+        self.lead = lead;
+        self
+    }
+
+    /// Limits the speed either side of the drivetrain is driven at.
+    pub const fn with_max_speed(mut self, max_speed: f64) -> Self {
+        self.max_speed = max_speed;
+        self
+    }
+
+    /// Sets whether the robot drives to the target backwards.
+    pub const fn with_reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
+    /// Sets how long the move can take before it is abandoned.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the point the robot is driving towards from `position`.
+    pub fn carrot(&self, position: Vec2) -> Vec2 {
+        let distance = position.distance(self.target.position);
+        if distance < self.close_distance {
+            return self.target.position;
+        }
+
+        // The carrot is behind the target in the direction the robot drives, which is the
+        // opposite way when reversed.
+        let lead = Vec2::from_polar(distance * self.lead, self.target.heading);
+        if self.reversed {
+            self.target.position + lead
+        } else {
+            self.target.position - lead
+        }
+    }
+
+    /// Returns `true` if the robot has settled at the target pose.
+    pub fn is_settled(&self) -> bool {
+        self.linear_pid.is_settled() && self.angular_pid.is_settled()
+    }
+
+    /// Returns `true` if the move has taken longer than its timeout.
+    pub fn is_timed_out(&self) -> bool {
+        self.elapsed >= self.timeout
+    }
+
+    /// Returns `true` once the move has settled or timed out.
+    pub fn is_finished(&self) -> bool {
+        self.is_settled() || self.is_timed_out()
+    }
+
+    /// Calculates the wheel speeds to drive towards the target from the robot's current pose,
+    /// using the time since the last update.
+    pub fn update(&mut self, pose: Pose2d) -> DifferentialWheelSpeeds {
+        let dt = time_since(&mut self.last_time);
+        self.update_with_dt(pose, dt)
+    }
+
+    /// Calculates the wheel speeds to drive towards the target from the robot's current pose,
+    /// given the time since the last update.
+    ///
+    /// Once the move is finished, both speeds are zero.
+    pub fn update_with_dt(&mut self, pose: Pose2d, dt: Duration) -> DifferentialWheelSpeeds {
+        self.elapsed += dt;
+        if self.is_finished() {
+            return DifferentialWheelSpeeds::default();
+        }
+
+        // When driving backwards, the robot chases the carrot as if it were facing the other
+        // way.
+        let flip = |rotation: Rotation2d| {
+            if self.reversed {
+                rotation + Rotation2d::from_radians(PI)
+            } else {
+                rotation
+            }
+        };
+        let heading = flip(pose.heading);
+        let direction = Vec2::from_polar(1.0, heading);
+
+        let close = pose.position.distance(self.target.position) < self.close_distance;
+        let carrot = self.carrot(pose.position);
+        let to_carrot = carrot - pose.position;
+
+        let (linear_error, angular_error) = if close {
+            (
+                to_carrot.dot(direction),
+                heading.shortest_difference(flip(self.target.heading)),
+            )
+        } else {
+            let angular_error = heading.shortest_difference(to_carrot.angle());
+            // Slow down while facing away from the carrot, so the robot turns towards it
+            // before driving off.
+            (to_carrot.length() * angular_error.cos(), angular_error)
+        };
+
+        let mut linear = update_on_error(&mut self.linear_pid, linear_error, dt);
+        let turn = update_on_error(&mut self.angular_pid, angular_error.radians(), dt);
+        if self.reversed {
+            linear = -linear;
+        }
+
+        // Slowing both sides equally keeps the robot on the same arc.
+        DifferentialWheelSpeeds {
+            left: linear - turn,
+            right: linear + turn,
+        }
+        .desaturate(self.max_speed)
+    }
+}
@@ -0,0 +1,308 @@
+//! Tracking wheel odometry.
+//!
+//! Odometry estimates the robot's position on the field by integrating the distance travelled by
+//! unpowered tracking wheels. An [`Odometry`] tracker is created from two parallel wheels, or
+//! one parallel wheel and a [`HeadingSource`] such as an [`InertialSensor`], with an optional
+//! perpendicular wheel for measuring sideways movement. It is then updated by calling
+//! [`Odometry::update`] repeatedly.
+//!
+//! Each update treats the movement since the last update as an arc of constant curvature, which
+//! is more accurate than assuming the robot moved in a straight line.
+//!
+//! Poses are measured in the same units as the wheel diameters.
+
+use core::{convert::Infallible, f64::consts::PI, fmt};
+
+use vexide_devices::{
+    position::Position,
+    smart::{imu::InertialError, motor::MotorError, InertialSensor, Motor, RotationSensor},
+    PortError,
+};
+
+use crate::geometry::{Pose2d, Rotation2d, Vec2};
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
+/// A device that measures the angular position of a tracking wheel.
+pub trait PositionSource {
+    /// The error returned when the position can't be read.
+    type Error;
+
+    /// Reads the current angular position.
+    fn position(&self) -> Result<Position, Self::Error>;
+}
+
+impl PositionSource for RotationSensor {
+    type Error = PortError;
+
+    fn position(&self) -> Result<Position, Self::Error> {
+        RotationSensor::position(self)
+    }
+}
+
+impl PositionSource for Motor {
+    type Error = MotorError;
+
+    fn position(&self) -> Result<Position, Self::Error> {
+        Motor::position(self)
+    }
+}
+
+/// A device that measures the robot's heading.
+pub trait HeadingSource {
+    /// The error returned when the heading can't be read.
+    type Error;
+
+    /// Reads the robot's total rotation in radians, counterclockwise positive.
+    ///
+    /// The value is unbounded, and only changes in it are used by odometry.
+    fn rotation(&self) -> Result<f64, Self::Error>;
+}
+
+impl HeadingSource for InertialSensor {
+    type Error = InertialError;
+
+    fn rotation(&self) -> Result<f64, Self::Error> {
+        // The sensor measures clockwise rotation in degrees.
+        Ok(-InertialSensor::rotation(self)?.to_radians())
+    }
+}
+
+/// A placeholder [`HeadingSource`] for trackers that calculate their heading from two parallel
+/// tracking wheels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoHeadingSource {}
+
+impl HeadingSource for NoHeadingSource {
+    type Error = Infallible;
+
+    fn rotation(&self) -> Result<f64, Self::Error> {
+        match *self {}
+    }
+}
+
+/// An unpowered wheel used to measure distance travelled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackingWheel<T> {
+    /// The device measuring the wheel's rotation.
+    pub source: T,
+    /// The diameter of the wheel.
+    pub wheel_diameter: f64,
+    /// The wheel's distance from the robot's tracking center.
+    ///
+    /// For parallel wheels, this is the sideways distance, positive to the left. For
+    /// perpendicular wheels, this is the forward distance, positive to the front.
+    pub offset: f64,
+}
+
+impl<T: PositionSource> TrackingWheel<T> {
+    /// Creates a new tracking wheel.
+    pub const fn new(source: T, wheel_diameter: f64, offset: f64) -> Self {
+        Self {
+            source,
+            wheel_diameter,
+            offset,
+        }
+    }
+
+    /// Reads the total distance travelled by the wheel.
+    pub fn distance(&self) -> Result<f64, T::Error> {
+        let rotations = self.source.position()?.into_degrees() / 360.0;
+        Ok(rotations * PI * self.wheel_diameter)
+    }
+}
+
+/// Errors that can occur when updating an [`Odometry`] tracker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OdometryError<W, H> {
+    /// A tracking wheel's position could not be read.
+    Wheel(W),
+    /// The heading source could not be read.
+    Heading(H),
+}
+
+impl<W: fmt::Display, H: fmt::Display> fmt::Display for OdometryError<W, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Wheel(error) => write!(f, "failed to read a tracking wheel: {error}"),
+            Self::Heading(error) => write!(f, "failed to read the heading source: {error}"),
+        }
+    }
+}
+
+impl<W: fmt::Debug + fmt::Display, H: fmt::Debug + fmt::Display> core::error::Error
+    for OdometryError<W, H>
+{
+}
+
+/// Sensor readings from a single [`Odometry::update`].
+#[derive(Debug, Clone, Copy)]
+struct Readings {
+    parallel: f64,
+    second_parallel: Option<f64>,
+    perpendicular: Option<f64>,
+    rotation: Option<f64>,
+}
+
+/// Tracks the robot's pose using tracking wheels.
+#[derive(Debug)]
+pub struct Odometry<T, H = NoHeadingSource> {
+    parallel: TrackingWheel<T>,
+    second_parallel: Option<TrackingWheel<T>>,
+    perpendicular: Option<TrackingWheel<T>>,
+    heading_source: Option<H>,
+    pose: Pose2d,
+    last_readings: Option<Readings>,
+}
+
+impl<T: PositionSource> Odometry<T> {
+    /// Creates a tracker that calculates its heading from two parallel tracking wheels.
+    ///
+    /// Without a perpendicular wheel, the robot is assumed not to move sideways.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both parallel wheels have the same offset.
+    pub fn new(
+        left: TrackingWheel<T>,
+        right: TrackingWheel<T>,
+        perpendicular: Option<TrackingWheel<T>>,
+    ) -> Self {
+        assert!(
+            left.offset != right.offset,
+            "parallel tracking wheels must have different offsets"
+        );
+
+        Self {
+            parallel: left,
+            second_parallel: Some(right),
+            perpendicular,
+            heading_source: None,
+            pose: Pose2d::ORIGIN,
+            last_readings: None,
+        }
+    }
+
+    /// Uses a heading source to measure the robot's heading instead of the difference between
+    /// the parallel tracking wheels.
+    pub fn with_heading_source<H: HeadingSource>(self, heading_source: H) -> Odometry<T, H> {
+        Odometry {
+            parallel: self.parallel,
+            second_parallel: self.second_parallel,
+            perpendicular: self.perpendicular,
+            heading_source: Some(heading_source),
+            pose: self.pose,
+            last_readings: None,
+        }
+    }
+}
+
+impl<T: PositionSource, H: HeadingSource> Odometry<T, H> {
+    /// Creates a tracker that measures its heading with a heading source such as an
+    /// [`InertialSensor`].
+    ///
+    /// Without a perpendicular wheel, the robot is assumed not to move sideways.
+    pub const fn with_single_wheel(
+        parallel: TrackingWheel<T>,
+        perpendicular: Option<TrackingWheel<T>>,
+        heading_source: H,
+    ) -> Self {
+        Self {
+            parallel,
+            second_parallel: None,
+            perpendicular,
+            heading_source: Some(heading_source),
+            pose: Pose2d::ORIGIN,
+            last_readings: None,
+        }
+    }
+
+    /// Returns the most recently calculated pose.
+    pub const fn pose(&self) -> Pose2d {
+        self.pose
+    }
+
+    /// Sets the current pose.
+    ///
+    /// Future updates integrate movement from this pose.
+    pub fn set_pose(&mut self, pose: Pose2d) {
+        self.pose = pose;
+    }
+
+    /// Resets the pose to the origin, facing the positive x axis.
+    pub fn reset(&mut self) {
+        self.set_pose(Pose2d::ORIGIN);
+    }
+
+    /// Reads the sensors and integrates the robot's movement since the last update, returning
+    /// the new pose.
+    ///
+    /// The first update only records the initial sensor readings. Updates should be made
+    /// frequently, as the movement between updates is assumed to follow a single arc.
+    pub fn update(&mut self) -> Result<Pose2d, OdometryError<T::Error, H::Error>> {
+        let readings = self.read()?;
+
+        if let Some(last) = self.last_readings.replace(readings) {
+            self.integrate(last, readings);
+        }
+
+        Ok(self.pose)
+    }
+
+    fn read(&self) -> Result<Readings, OdometryError<T::Error, H::Error>> {
+        let distance = |wheel: &TrackingWheel<T>| wheel.distance().map_err(OdometryError::Wheel);
+
+        Ok(Readings {
+            parallel: distance(&self.parallel)?,
+            second_parallel: self.second_parallel.as_ref().map(distance).transpose()?,
+            perpendicular: self.perpendicular.as_ref().map(distance).transpose()?,
+            rotation: self
+                .heading_source
+                .as_ref()
+                .map(|source| source.rotation().map_err(OdometryError::Heading))
+                .transpose()?,
+        })
+    }
+
+    fn integrate(&mut self, last: Readings, readings: Readings) {
+        let delta =
+            |now: Option<f64>, then: Option<f64>| now.zip(then).map(|(now, then)| now - then);
+
+        let parallel = readings.parallel - last.parallel;
+        let second_parallel = delta(readings.second_parallel, last.second_parallel)
+            .zip(self.second_parallel.as_ref().map(|wheel| wheel.offset));
+        let perpendicular = delta(readings.perpendicular, last.perpendicular).unwrap_or(0.0);
+
+        // Points to the left of the tracking center move backwards as the robot turns
+        // counterclockwise, so a wheel at offset `d` travels `forward - d * delta_heading`.
+        let delta_heading = match (delta(readings.rotation, last.rotation), second_parallel) {
+            (Some(delta_heading), _) => delta_heading,
+            (None, Some((second_parallel, second_offset))) => {
+                (second_parallel - parallel) / (self.parallel.offset - second_offset)
+            }
+            (None, None) => 0.0,
+        };
+
+        let mut forward = parallel + self.parallel.offset * delta_heading;
+        if let Some((second_parallel, second_offset)) = second_parallel {
+            forward = (forward + second_parallel + second_offset * delta_heading) / 2.0;
+        }
+        let sideways = match &self.perpendicular {
+            Some(wheel) => perpendicular - wheel.offset * delta_heading,
+            None => 0.0,
+        };
+
+        // The chord of an arc is shorter than the arc itself, and points halfway between the
+        // starting and ending headings.
+        let chord_scale = if delta_heading.abs() < 1e-9 {
+            1.0
+        } else {
+            (delta_heading / 2.0).sin() / (delta_heading / 2.0)
+        };
+        let chord = Vec2::new(forward, sideways) * chord_scale;
+        let delta_heading = Rotation2d::from_radians(delta_heading);
+
+        self.pose.position += chord.rotate_by(self.pose.heading + delta_heading * 0.5);
+        self.pose.heading += delta_heading;
+    }
+}
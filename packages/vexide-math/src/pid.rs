@@ -2,67 +2,198 @@
 //!
 //! PID controllers are first created with [`PidController::new`]
 //! and then can be utilized by calling [`PidController::update`] repeatedly.
+//!
+//! For deterministic behavior, such as when testing off the brain, use
+//! [`PidController::update_with_dt`] to provide the time between updates explicitly.
 
 use core::time::Duration;
 
+use vexide_core::time::Instant;
+
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
 /// A proportional–integral–derivative controller.
 ///
 /// This controller is used to smoothly move motors to a certain point,
 /// and allows for feedback-based power adjustments. This is desirable
 /// over just setting the motor power, as it can be tuned to make the
 /// motor stop in exactly the right position without overshooting.
+///
+/// The derivative term is calculated from the rate of change of the measurement rather than the
+/// error, so changing the setpoint doesn't cause a spike in the output.
 #[derive(Debug, Clone, Copy)]
 pub struct PidController {
     /// Proportional constant. This is multiplied by the error to get the
     /// proportional component of the output.
-    pub kp: f32,
+    pub kp: f64,
     /// Integral constant. This accounts for the past values of the error.
-    pub ki: f32,
+    pub ki: f64,
     /// Derivative constant. This allows you to change the motor behavior
-    /// based on the rate of change of the error (predicting future values).
-    pub kd: f32,
+    /// based on the rate of change of the measurement (predicting future values).
+    pub kd: f64,
 
-    last_time: vexide_core::time::Instant,
-    last_position: f32,
-    i: f32,
+    /// The largest magnitude the integral component of the output can reach.
+    ///
+    /// Limiting the integral prevents it from building up while the output is saturated. The
+    /// sign of the limit is ignored, and a NaN limit is treated as no limit. There is no limit by
+    /// default.
+    pub integral_limit: f64,
+    /// Whether the integral is reset when the error changes sign.
+    ///
+    /// This stops the integral from pushing the system past the setpoint after it has been
+    /// crossed. Enabled by default.
+    pub reset_integral_on_sign_change: bool,
+    /// Smoothing applied to the derivative in the range `[0.0, 1.0)`.
+    ///
+    /// Higher values filter out more noise from the measurement at the cost of a slower
+    /// response. The default of `0.0` applies no filtering.
+    pub derivative_filter: f64,
+    /// The largest magnitude the output can reach. The sign of the limit is ignored, and a NaN
+    /// limit is treated as no limit. There is no limit by default.
+    pub output_limit: f64,
+
+    /// The largest error at which the controller is considered settled.
+    pub settle_tolerance: f64,
+    /// How long the error must stay within [`PidController::settle_tolerance`] before the
+    /// controller is considered settled.
+    pub settle_time: Duration,
+
+    integral: f64,
+    derivative: f64,
+    last_error: f64,
+    last_measurement: Option<f64>,
+    last_time: Option<Instant>,
+    settled_for: Duration,
 }
 
 impl PidController {
     /// Create a new PID controller with the given constants.
-    pub fn new(kp: f32, ki: f32, kd: f32) -> Self {
+    pub const fn new(kp: f64, ki: f64, kd: f64) -> Self {
         Self {
             kp,
             ki,
             kd,
-            last_time: vexide_core::time::Instant::now(),
-            last_position: 0.0,
-            i: 0.0,
+            integral_limit: f64::INFINITY,
+            reset_integral_on_sign_change: true,
+            derivative_filter: 0.0,
+            output_limit: f64::INFINITY,
+            settle_tolerance: 0.0,
+            settle_time: Duration::ZERO,
+            integral: 0.0,
+            derivative: 0.0,
+            last_error: 0.0,
+            last_measurement: None,
+            last_time: None,
+            settled_for: Duration::ZERO,
         }
     }
 
-    /// Update the PID controller with the current setpoint and position.
-    pub fn update(&mut self, setpoint: f32, position: f32) -> f32 {
-        let mut delta_time = self.last_time.elapsed();
-        if delta_time.is_zero() {
-            delta_time += Duration::from_micros(1);
-        }
-        let error = setpoint - position;
+    /// Limits the magnitude of the integral component of the output.
+    pub const fn with_integral_limit(mut self, limit: f64) -> Self {
+        self.integral_limit = limit;
+        self
+    }
+
+    /// Limits the magnitude of the output.
+    pub const fn with_output_limit(mut self, limit: f64) -> Self {
+        self.output_limit = limit;
+        self
+    }
+
+    /// Sets the smoothing applied to the derivative, in the range `[0.0, 1.0)`.
+    pub const fn with_derivative_filter(mut self, filter: f64) -> Self {
+        self.derivative_filter = filter;
+        self
+    }
+
+    /// Sets how close to the setpoint, and for how long, the measurement must be for the
+    /// controller to be considered settled.
+    pub const fn with_settle_condition(mut self, tolerance: f64, time: Duration) -> Self {
+        self.settle_tolerance = tolerance;
+        self.settle_time = time;
+        self
+    }
 
-        self.i += error * delta_time.as_secs_f32();
+    /// Update the PID controller with the current setpoint and measurement, using the time
+    /// since the last update.
+    pub fn update(&mut self, setpoint: f64, measurement: f64) -> f64 {
+        let now = Instant::now();
+        let dt = self
+            .last_time
+            .map_or(Duration::ZERO, |last_time| now.duration_since(last_time));
+        self.last_time = Some(now);
 
-        let p = self.kp * error;
-        let i = self.ki * self.i;
+        self.update_with_dt(setpoint, measurement, dt)
+    }
+
+    /// Update the PID controller with the current setpoint and measurement, given the time
+    /// since the last update.
+    pub fn update_with_dt(&mut self, setpoint: f64, measurement: f64, dt: Duration) -> f64 {
+        let error = setpoint - measurement;
+        let dt_secs = dt.as_secs_f64();
+
+        if self.reset_integral_on_sign_change && error * self.last_error < 0.0 {
+            self.integral = 0.0;
+        }
+        self.integral = clamp_magnitude(
+            self.integral + self.ki * error * dt_secs,
+            self.integral_limit,
+        );
 
-        let mut d = (position - self.last_position) / delta_time.as_secs_f32();
-        if d.is_nan() {
-            d = 0.0
+        if let Some(last_measurement) = self.last_measurement {
+            if dt_secs > 0.0 {
+                let derivative = -(measurement - last_measurement) / dt_secs;
+                self.derivative = self.derivative_filter * self.derivative
+                    + (1.0 - self.derivative_filter) * derivative;
+            }
         }
 
-        let output = p + i + d;
+        if error.abs() <= self.settle_tolerance {
+            self.settled_for += dt;
+        } else {
+            self.settled_for = Duration::ZERO;
+        }
+
+        self.last_error = error;
+        self.last_measurement = Some(measurement);
+
+        clamp_magnitude(
+            self.kp * error + self.integral + self.kd * self.derivative,
+            self.output_limit,
+        )
+    }
+
+    /// Returns `true` if the error has been within the settle tolerance for at least the
+    /// settle time.
+    pub fn is_settled(&self) -> bool {
+        self.last_measurement.is_some()
+            && self.last_error.abs() <= self.settle_tolerance
+            && self.settled_for >= self.settle_time
+    }
 
-        self.last_position = position;
-        self.last_time = vexide_core::time::Instant::now();
+    /// Returns the error from the most recent update.
+    pub const fn error(&self) -> f64 {
+        self.last_error
+    }
 
-        output
+    /// Clears the integral, derivative and settle state, as if the controller had just been
+    /// created.
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.derivative = 0.0;
+        self.last_error = 0.0;
+        self.last_measurement = None;
+        self.last_time = None;
+        self.settled_for = Duration::ZERO;
     }
 }
+
+/// Limits the magnitude of `value` to `limit`.
+///
+/// Unlike [`f64::clamp`], this doesn't panic if the limit is negative or NaN, since the limits
+/// are public fields that can be set to anything.
+fn clamp_magnitude(value: f64, limit: f64) -> f64 {
+    let limit = limit.abs();
+    value.max(-limit).min(limit)
+}
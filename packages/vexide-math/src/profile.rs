@@ -0,0 +1,283 @@
+//! Motion profiles.
+//!
+//! A motion profile plans a smooth move from one position to another without exceeding a
+//! maximum velocity and acceleration. Profiles are sampled at the time elapsed since the start
+//! of the move with [`MotionProfile::sample`], which gives the position, velocity and
+//! acceleration the mechanism should have at that time.
+//!
+//! [`TrapezoidalProfile`] accelerates and decelerates at a constant rate, while
+//! [`SCurveProfile`] also limits jerk (the rate of change of acceleration), which produces
+//! smoother motion at the cost of taking slightly longer.
+//!
+//! Samples can be fed to a feedforward controller, such as
+//! [`MotorFeedforwardController`](crate::feedforward::MotorFeedforwardController), and a
+//! feedback controller following the sampled position.
+
+use core::time::Duration;
+
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
+/// The state of a mechanism at a point in a motion profile.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ProfileState {
+    /// Position.
+    pub position: f64,
+    /// Velocity, in position units per second.
+    pub velocity: f64,
+    /// Acceleration, in position units per second squared.
+    pub acceleration: f64,
+}
+
+/// A planned move that can be sampled over time.
+pub trait MotionProfile {
+    /// Returns the state the mechanism should be in once `elapsed` has passed since the start of
+    /// the move.
+    ///
+    /// Times past the end of the profile return the final state, at rest at the target.
+    fn sample(&self, elapsed: Duration) -> ProfileState;
+
+    /// Returns the total time taken by the move.
+    fn duration(&self) -> Duration;
+
+    /// Returns `true` if the move is complete once `elapsed` has passed.
+    fn is_finished(&self, elapsed: Duration) -> bool {
+        elapsed >= self.duration()
+    }
+}
+
+/// A part of a profile with constant jerk.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Segment {
+    /// Length of the segment in seconds.
+    duration: f64,
+    /// Acceleration at the start of the segment.
+    acceleration: f64,
+    /// Jerk throughout the segment.
+    jerk: f64,
+}
+
+/// A rest-to-rest profile made of up to seven constant-jerk segments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Segments {
+    start: f64,
+    end: f64,
+    /// `1.0` when moving in the positive direction, `-1.0` otherwise.
+    direction: f64,
+    segments: [Segment; 7],
+    duration: f64,
+}
+
+impl Segments {
+    fn new(start: f64, end: f64, segments: [Segment; 7]) -> Self {
+        Self {
+            start,
+            end,
+            direction: if end >= start { 1.0 } else { -1.0 },
+            segments,
+            duration: segments.iter().map(|segment| segment.duration).sum(),
+        }
+    }
+
+    fn sample(&self, elapsed: Duration) -> ProfileState {
+        let mut remaining = elapsed.as_secs_f64();
+        if remaining >= self.duration {
+            return ProfileState {
+                position: self.end,
+                velocity: 0.0,
+                acceleration: 0.0,
+            };
+        }
+
+        let mut position = 0.0;
+        let mut velocity = 0.0;
+        let mut acceleration = 0.0;
+
+        for segment in &self.segments {
+            let t = remaining.min(segment.duration);
+            let a = segment.acceleration;
+            let j = segment.jerk;
+
+            position += velocity * t + a * t * t / 2.0 + j * t * t * t / 6.0;
+            velocity += a * t + j * t * t / 2.0;
+            acceleration = a + j * t;
+
+            remaining -= t;
+            if remaining <= 0.0 {
+                break;
+            }
+        }
+
+        ProfileState {
+            position: self.start + self.direction * position,
+            velocity: self.direction * velocity,
+            acceleration: self.direction * acceleration,
+        }
+    }
+}
+
+/// A motion profile that accelerates at a constant rate to a cruising velocity, then
+/// decelerates at the same rate to stop at the target.
+///
+/// Short moves that can't reach the maximum velocity form a triangle instead of a trapezoid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrapezoidalProfile {
+    segments: Segments,
+}
+
+impl TrapezoidalProfile {
+    /// Plans a move from `start` to `end`, starting and ending at rest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_velocity` or `max_acceleration` isn't positive.
+    pub fn new(start: f64, end: f64, max_velocity: f64, max_acceleration: f64) -> Self {
+        assert!(
+            max_velocity > 0.0 && max_acceleration > 0.0,
+            "motion profile constraints must be positive"
+        );
+
+        let distance = (end - start).abs();
+
+        // Accelerating to the cruising velocity and back down covers v² / a.
+        let peak_velocity = max_velocity.min((distance * max_acceleration).sqrt());
+        let accel_time = peak_velocity / max_acceleration;
+        let cruise_time = if peak_velocity > 0.0 {
+            (distance - peak_velocity * accel_time) / peak_velocity
+        } else {
+            0.0
+        };
+
+        let mut segments = [Segment::default(); 7];
+        segments[0] = Segment {
+            duration: accel_time,
+            acceleration: max_acceleration,
+            jerk: 0.0,
+        };
+        segments[1] = Segment {
+            duration: cruise_time.max(0.0),
+            acceleration: 0.0,
+            jerk: 0.0,
+        };
+        segments[2] = Segment {
+            duration: accel_time,
+            acceleration: -max_acceleration,
+            jerk: 0.0,
+        };
+
+        Self {
+            segments: Segments::new(start, end, segments),
+        }
+    }
+}
+
+impl MotionProfile for TrapezoidalProfile {
+    fn sample(&self, elapsed: Duration) -> ProfileState {
+        self.segments.sample(elapsed)
+    }
+
+    fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.segments.duration)
+    }
+}
+
+/// A motion profile that limits jerk as well as acceleration, so acceleration ramps up and down
+/// smoothly instead of changing instantly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SCurveProfile {
+    segments: Segments,
+}
+
+impl SCurveProfile {
+    /// Plans a move from `start` to `end`, starting and ending at rest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_velocity`, `max_acceleration` or `max_jerk` isn't positive.
+    pub fn new(
+        start: f64,
+        end: f64,
+        max_velocity: f64,
+        max_acceleration: f64,
+        max_jerk: f64,
+    ) -> Self {
+        assert!(
+            max_velocity > 0.0 && max_acceleration > 0.0 && max_jerk > 0.0,
+            "motion profile constraints must be positive"
+        );
+
+        let distance = (end - start).abs();
+
+        // The distance covered by speeding up to `velocity` and slowing back down to rest.
+        let ramp_distance = |velocity: f64| {
+            let (jerk_time, accel_time) = Self::ramp_times(velocity, max_acceleration, max_jerk);
+            velocity * (2.0 * jerk_time + accel_time)
+        };
+
+        let peak_velocity = if ramp_distance(max_velocity) <= distance {
+            max_velocity
+        } else {
+            // The ramp distance grows with the peak velocity, so the fastest peak that fits
+            // within the move can be found by bisection.
+            let (mut low, mut high) = (0.0, max_velocity);
+            for _ in 0..64 {
+                let mid = (low + high) / 2.0;
+                if ramp_distance(mid) <= distance {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            low
+        };
+
+        let (jerk_time, accel_time) = Self::ramp_times(peak_velocity, max_acceleration, max_jerk);
+        let peak_acceleration = max_jerk * jerk_time;
+        let cruise_time = if peak_velocity > 0.0 {
+            ((distance - ramp_distance(peak_velocity)) / peak_velocity).max(0.0)
+        } else {
+            0.0
+        };
+
+        let segment = |duration, acceleration, jerk| Segment {
+            duration,
+            acceleration,
+            jerk,
+        };
+        let segments = [
+            segment(jerk_time, 0.0, max_jerk),
+            segment(accel_time, peak_acceleration, 0.0),
+            segment(jerk_time, peak_acceleration, -max_jerk),
+            segment(cruise_time, 0.0, 0.0),
+            segment(jerk_time, 0.0, -max_jerk),
+            segment(accel_time, -peak_acceleration, 0.0),
+            segment(jerk_time, -peak_acceleration, max_jerk),
+        ];
+
+        Self {
+            segments: Segments::new(start, end, segments),
+        }
+    }
+
+    /// Returns the time spent ramping acceleration up (or down), and the time spent at constant
+    /// acceleration, when speeding up from rest to `velocity`.
+    fn ramp_times(velocity: f64, max_acceleration: f64, max_jerk: f64) -> (f64, f64) {
+        if velocity * max_jerk <= max_acceleration * max_acceleration {
+            // The maximum acceleration is never reached.
+            ((velocity / max_jerk).sqrt(), 0.0)
+        } else {
+            let jerk_time = max_acceleration / max_jerk;
+            (jerk_time, velocity / max_acceleration - jerk_time)
+        }
+    }
+}
+
+impl MotionProfile for SCurveProfile {
+    fn sample(&self, elapsed: Duration) -> ProfileState {
+        self.segments.sample(elapsed)
+    }
+
+    fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.segments.duration)
+    }
+}
@@ -0,0 +1,257 @@
+//! Pure pursuit path following.
+//!
+//! Pure pursuit steers a differential drivetrain along a path of waypoints by repeatedly driving
+//! in an arc towards a point on the path a fixed distance ahead of the robot, called the
+//! lookahead point. Larger lookahead distances follow the path more smoothly but cut corners
+//! more.
+//!
+//! A [`PurePursuit`] controller is created with the path to follow and then updated with the
+//! robot's current pose, such as from [`Odometry`](crate::odometry::Odometry), until
+//! [`PurePursuit::is_finished`] returns `true`.
+
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+use crate::geometry::{Pose2d, Rotation2d, Vec2};
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
+/// Wheel velocities calculated by a [`PurePursuit`] controller.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PurePursuitOutput {
+    /// The velocity of the left side of the drivetrain.
+    pub left_velocity: f64,
+    /// The velocity of the right side of the drivetrain.
+    pub right_velocity: f64,
+    /// The curvature of the arc being driven, positive when turning counterclockwise.
+    pub curvature: f64,
+    /// The point on the path being driven towards.
+    pub lookahead_point: Vec2,
+}
+
+/// A pure pursuit path follower for differential drivetrains.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurePursuit {
+    path: Vec<Vec2>,
+    /// The distance from the robot to the point on the path it drives towards.
+    pub lookahead: f64,
+    /// The distance between the left and right wheels.
+    pub track_width: f64,
+    /// The largest velocity any wheel is driven at.
+    pub max_velocity: f64,
+    /// How close the robot must be to the end of the path for it to be finished.
+    pub end_tolerance: f64,
+    /// Whether the robot follows the path driving backwards.
+    pub reversed: bool,
+    /// The index of the path segment the lookahead point was last found on.
+    segment: usize,
+    finished: bool,
+}
+
+impl PurePursuit {
+    /// Creates a controller following a path through `waypoints`.
+    ///
+    /// The robot finishes once it is within `lookahead / 4` of the last waypoint by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `waypoints` is empty or `lookahead` isn't positive.
+    pub fn new(
+        waypoints: impl IntoIterator<Item = impl Into<Vec2>>,
+        lookahead: f64,
+        track_width: f64,
+        max_velocity: f64,
+    ) -> Self {
+        let path: Vec<Vec2> = waypoints.into_iter().map(Into::into).collect();
+        assert!(
+            !path.is_empty(),
+            "pure pursuit paths need at least one waypoint"
+        );
+        assert!(lookahead > 0.0, "the lookahead distance must be positive");
+
+        Self {
+            path,
+            lookahead,
+            track_width,
+            max_velocity,
+            end_tolerance: lookahead / 4.0,
+            reversed: false,
+            segment: 0,
+            finished: false,
+        }
+    }
+
+    /// Sets whether the robot follows the path driving backwards.
+    pub const fn with_reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
+    /// Sets how close the robot must be to the end of the path for it to be finished.
+    pub const fn with_end_tolerance(mut self, tolerance: f64) -> Self {
+        self.end_tolerance = tolerance;
+        self
+    }
+
+    /// Returns the waypoints being followed.
+    pub fn path(&self) -> &[Vec2] {
+        &self.path
+    }
+
+    /// Returns `true` once the robot has reached the end of the path.
+    pub const fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Restarts the path from the first waypoint.
+    pub fn reset(&mut self) {
+        self.segment = 0;
+        self.finished = false;
+    }
+
+    /// Calculates the wheel velocities to drive the robot towards the path from its current
+    /// pose.
+    ///
+    /// Once the robot reaches the end of the path, both wheel velocities are zero.
+    pub fn update(&mut self, pose: Pose2d) -> PurePursuitOutput {
+        let position = pose.position;
+        let end = self.path[self.path.len() - 1];
+
+        if self.finished || self.has_reached_end(position) {
+            self.finished = true;
+            return PurePursuitOutput {
+                left_velocity: 0.0,
+                right_velocity: 0.0,
+                curvature: 0.0,
+                lookahead_point: end,
+            };
+        }
+
+        let lookahead_point = self.lookahead_point(position);
+
+        // When driving backwards, the robot follows the path as if it were facing the other way.
+        let heading = if self.reversed {
+            pose.heading + Rotation2d::from_radians(PI)
+        } else {
+            pose.heading
+        };
+
+        // Transform the lookahead point into the robot's frame, where the robot faces +x.
+        let local = Pose2d::new(position, heading).to_local(lookahead_point);
+        let distance_squared = local.length_squared();
+
+        let curvature = if distance_squared > 0.0 {
+            2.0 * local.y / distance_squared
+        } else {
+            0.0
+        };
+
+        let mut left = 1.0 - curvature * self.track_width / 2.0;
+        let mut right = 1.0 + curvature * self.track_width / 2.0;
+
+        // Slow both sides down equally so neither exceeds the maximum velocity.
+        let scale = self.max_velocity / left.abs().max(right.abs());
+        left *= scale;
+        right *= scale;
+
+        if self.reversed {
+            (left, right) = (-right, -left);
+        }
+
+        PurePursuitOutput {
+            left_velocity: left,
+            right_velocity: right,
+            curvature: if self.reversed { -curvature } else { curvature },
+            lookahead_point,
+        }
+    }
+
+    /// Checks whether the robot is within the end tolerance of the last waypoint, or has driven
+    /// past it.
+    fn has_reached_end(&self, position: Vec2) -> bool {
+        let end = self.path[self.path.len() - 1];
+        if position.distance(end) <= self.end_tolerance {
+            return true;
+        }
+
+        // The robot has passed the end if it is beyond the end of the final segment while
+        // following it.
+        if self.path.len() >= 2 && self.segment == self.path.len() - 2 {
+            let start = self.path[self.path.len() - 2];
+            return (end - start).dot(position - end) > 0.0
+                && position.distance(end) <= self.lookahead;
+        }
+
+        false
+    }
+
+    /// Finds the furthest point along the path that is one lookahead distance from the robot.
+    ///
+    /// The search starts from the segment the last lookahead point was on, so the robot never
+    /// turns back towards parts of the path it has already followed.
+    fn lookahead_point(&mut self, position: Vec2) -> Vec2 {
+        let end = self.path[self.path.len() - 1];
+        if position.distance(end) <= self.lookahead {
+            self.segment = self.path.len().saturating_sub(2);
+            return end;
+        }
+
+        let mut found = None;
+        for index in self.segment..self.path.len().saturating_sub(1) {
+            if let Some(point) = self.intersection(self.path[index], self.path[index + 1], position)
+            {
+                found = Some((index, point));
+            }
+        }
+
+        match found {
+            Some((index, point)) => {
+                self.segment = index;
+                point
+            }
+            // The robot is too far from the path to intersect it, so head back to the closest
+            // point on the current segment.
+            None => match self.path.get(self.segment + 1) {
+                Some(&next) => closest_point(self.path[self.segment], next, position),
+                None => end,
+            },
+        }
+    }
+
+    /// Returns the intersection of the lookahead circle with the segment from `start` to `end`
+    /// that is furthest along the segment.
+    fn intersection(&self, start: Vec2, end: Vec2, center: Vec2) -> Option<Vec2> {
+        let direction = end - start;
+        let offset = start - center;
+
+        let a = direction.length_squared();
+        if a == 0.0 {
+            return None;
+        }
+        let b = 2.0 * offset.dot(direction);
+        let c = offset.length_squared() - self.lookahead * self.lookahead;
+
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let root = discriminant.sqrt();
+        [(-b + root) / (2.0 * a), (-b - root) / (2.0 * a)]
+            .into_iter()
+            .find(|t| (0.0..=1.0).contains(t))
+            .map(|t| start.lerp(end, t))
+    }
+}
+
+/// Returns the point on the segment from `start` to `end` closest to `point`.
+fn closest_point(start: Vec2, end: Vec2, point: Vec2) -> Vec2 {
+    let direction = end - start;
+    let length_squared = direction.length_squared();
+    if length_squared == 0.0 {
+        return start;
+    }
+
+    let t = ((point - start).dot(direction) / length_squared).clamp(0.0, 1.0);
+    start.lerp(end, t)
+}
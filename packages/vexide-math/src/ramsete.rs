@@ -0,0 +1,110 @@
+//! RAMSETE trajectory tracking.
+//!
+//! The RAMSETE controller makes a differential drivetrain follow a [`Trajectory`] by correcting
+//! the trajectory's velocities based on how far the robot has drifted from the desired pose.
+//! Unlike simpler followers, it corrects errors in heading and sideways position as well as
+//! forward position.
+//!
+//! The controller outputs the chassis velocities to drive at. These can be converted to wheel
+//! velocities with [`RamseteOutput::wheel_velocities`], and then to voltages with a
+//! [`MotorFeedforwardController`](crate::feedforward::MotorFeedforwardController) for each side
+//! of the drivetrain.
+
+use core::time::Duration;
+
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+use crate::{
+    geometry::Pose2d,
+    trajectory::{Trajectory, TrajectoryState},
+};
+
+/// Chassis velocities calculated by a [`RamseteController`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RamseteOutput {
+    /// The forward velocity to drive at.
+    pub linear_velocity: f64,
+    /// The rate to turn at in radians per second, counterclockwise positive.
+    pub angular_velocity: f64,
+}
+
+impl RamseteOutput {
+    /// Returns the left and right wheel velocities of a differential drivetrain with the given
+    /// distance between its left and right wheels.
+    pub fn wheel_velocities(&self, track_width: f64) -> (f64, f64) {
+        let turn = self.angular_velocity * track_width / 2.0;
+        (self.linear_velocity - turn, self.linear_velocity + turn)
+    }
+}
+
+/// A RAMSETE nonlinear trajectory tracking controller.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RamseteController {
+    /// How aggressively the controller corrects errors, like a proportional gain. Must be
+    /// positive.
+    pub b: f64,
+    /// How much the correction is damped, in the range `(0.0, 1.0)`.
+    pub zeta: f64,
+}
+
+impl RamseteController {
+    /// Creates a controller with the given gains.
+    pub const fn new(b: f64, zeta: f64) -> Self {
+        Self { b, zeta }
+    }
+
+    /// Calculates the chassis velocities that move the robot from its current pose towards the
+    /// desired state.
+    ///
+    /// Poses are measured in the same distance units as the desired linear velocity.
+    pub fn calculate(&self, pose: Pose2d, desired: TrajectoryState) -> RamseteOutput {
+        let velocity = desired.linear_velocity;
+        let angular_velocity = desired.angular_velocity;
+
+        // Error in the robot's frame, where the robot faces +x.
+        let error = pose.to_local(desired.pose.position);
+        let heading_error = pose
+            .heading
+            .shortest_difference(desired.pose.heading)
+            .radians();
+
+        let gain = 2.0
+            * self.zeta
+            * (angular_velocity * angular_velocity + self.b * velocity * velocity).sqrt();
+
+        RamseteOutput {
+            linear_velocity: velocity * heading_error.cos() + gain * error.x,
+            angular_velocity: angular_velocity
+                + gain * heading_error
+                + self.b * velocity * sinc(heading_error) * error.y,
+        }
+    }
+
+    /// Samples a trajectory at `elapsed` and calculates the chassis velocities to follow it
+    /// from the robot's current pose.
+    pub fn follow(
+        &self,
+        trajectory: &impl Trajectory,
+        elapsed: Duration,
+        pose: Pose2d,
+    ) -> RamseteOutput {
+        self.calculate(pose, trajectory.sample(elapsed))
+    }
+}
+
+impl Default for RamseteController {
+    /// Creates a controller with `b = 2.0` and `zeta = 0.7`, which work well for most robots
+    /// when distances are measured in meters.
+    fn default() -> Self {
+        Self::new(2.0, 0.7)
+    }
+}
+
+/// `sin(x) / x`, which approaches 1 as `x` approaches 0.
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        x.sin() / x
+    }
+}
@@ -0,0 +1,326 @@
+//! Spline path generation.
+//!
+//! Splines are smooth curves between two poses, leaving the first pose and arriving at the
+//! second along their headings. A [`Path`] joins a spline between each pair of waypoints and
+//! samples it at even distances along the curve, so it can be followed directly, or turned into
+//! a time-parameterized [`SampledTrajectory`](crate::trajectory::SampledTrajectory) that respects
+//! a robot's velocity and acceleration limits.
+//!
+//! [`CubicBezier`] splines are simple and cheap to evaluate, but their curvature can jump where
+//! two splines meet. [`QuinticHermite`] splines keep curvature continuous across waypoints,
+//! which is easier for a robot to follow.
+
+use alloc::vec::Vec;
+
+use crate::geometry::{Pose2d, Rotation2d, Vec2};
+
+/// The number of straight pieces each spline is divided into when measuring its length.
+const ARC_LENGTH_STEPS: usize = 256;
+
+/// A smooth curve parameterized by `t` in the range `[0.0, 1.0]`.
+pub trait Spline {
+    /// Returns the point on the curve at `t`.
+    fn point(&self, t: f64) -> Vec2;
+
+    /// Returns the first derivative of the curve with respect to `t`.
+    fn derivative(&self, t: f64) -> Vec2;
+
+    /// Returns the second derivative of the curve with respect to `t`.
+    fn second_derivative(&self, t: f64) -> Vec2;
+
+    /// Returns the direction the curve is travelling in at `t`.
+    fn heading(&self, t: f64) -> Rotation2d {
+        self.derivative(t).angle()
+    }
+
+    /// Returns the curvature (the inverse of the turning radius) of the curve at `t`, positive
+    /// when turning counterclockwise.
+    fn curvature(&self, t: f64) -> f64 {
+        let derivative = self.derivative(t);
+        let speed = derivative.length();
+        if speed == 0.0 {
+            return 0.0;
+        }
+        derivative.cross(self.second_derivative(t)) / (speed * speed * speed)
+    }
+}
+
+/// A polynomial curve `c[0] + c[1] t + c[2] t² + ...`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Polynomial<const N: usize> {
+    coefficients: [Vec2; N],
+}
+
+impl<const N: usize> Polynomial<N> {
+    fn point(&self, t: f64) -> Vec2 {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Vec2::ZERO, |sum, &coefficient| sum * t + coefficient)
+    }
+
+    fn derivative(&self, t: f64) -> Vec2 {
+        self.coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .rev()
+            .fold(Vec2::ZERO, |sum, (power, &coefficient)| {
+                sum * t + coefficient * power as f64
+            })
+    }
+
+    fn second_derivative(&self, t: f64) -> Vec2 {
+        self.coefficients
+            .iter()
+            .enumerate()
+            .skip(2)
+            .rev()
+            .fold(Vec2::ZERO, |sum, (power, &coefficient)| {
+                sum * t + coefficient * (power * (power - 1)) as f64
+            })
+    }
+}
+
+/// Returns the tangent used to leave or arrive at a waypoint, scaled by the distance between
+/// the waypoints so the curve neither loops nor flattens out.
+fn tangent(pose: Pose2d, distance: f64) -> Vec2 {
+    Vec2::from_polar(distance, pose.heading)
+}
+
+/// A cubic Bezier curve, defined by its two endpoints and two control points that pull the
+/// curve towards them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezier {
+    polynomial: Polynomial<4>,
+}
+
+impl CubicBezier {
+    /// Creates a curve from `start` to `end`, pulled towards `control_1` and then `control_2`.
+    pub fn new(start: Vec2, control_1: Vec2, control_2: Vec2, end: Vec2) -> Self {
+        Self {
+            polynomial: Polynomial {
+                coefficients: [
+                    start,
+                    (control_1 - start) * 3.0,
+                    (start - control_1 * 2.0 + control_2) * 3.0,
+                    end - start + (control_1 - control_2) * 3.0,
+                ],
+            },
+        }
+    }
+
+    /// Creates a curve that leaves `start` and arrives at `end` along their headings.
+    pub fn from_poses(start: Pose2d, end: Pose2d) -> Self {
+        let distance = start.position.distance(end.position);
+        Self::new(
+            start.position,
+            start.position + tangent(start, distance) / 3.0,
+            end.position - tangent(end, distance) / 3.0,
+            end.position,
+        )
+    }
+}
+
+impl Spline for CubicBezier {
+    fn point(&self, t: f64) -> Vec2 {
+        self.polynomial.point(t)
+    }
+
+    fn derivative(&self, t: f64) -> Vec2 {
+        self.polynomial.derivative(t)
+    }
+
+    fn second_derivative(&self, t: f64) -> Vec2 {
+        self.polynomial.second_derivative(t)
+    }
+}
+
+/// A quintic Hermite spline, defined by the position, first derivative and second derivative at
+/// each of its endpoints.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuinticHermite {
+    polynomial: Polynomial<6>,
+}
+
+impl QuinticHermite {
+    /// Creates a spline with the given position, first derivative and second derivative at
+    /// each end.
+    pub fn new(
+        start: Vec2,
+        start_derivative: Vec2,
+        start_second_derivative: Vec2,
+        end: Vec2,
+        end_derivative: Vec2,
+        end_second_derivative: Vec2,
+    ) -> Self {
+        let (p0, v0, a0) = (start, start_derivative, start_second_derivative);
+        let (p1, v1, a1) = (end, end_derivative, end_second_derivative);
+
+        Self {
+            polynomial: Polynomial {
+                coefficients: [
+                    p0,
+                    v0,
+                    a0 * 0.5,
+                    (p1 - p0) * 10.0 - v0 * 6.0 - v1 * 4.0 - a0 * 1.5 + a1 * 0.5,
+                    (p0 - p1) * 15.0 + v0 * 8.0 + v1 * 7.0 + a0 * 1.5 - a1,
+                    (p1 - p0) * 6.0 - v0 * 3.0 - v1 * 3.0 - a0 * 0.5 + a1 * 0.5,
+                ],
+            },
+        }
+    }
+
+    /// Creates a spline that leaves `start` and arrives at `end` along their headings, without
+    /// curving at either end.
+    ///
+    /// Because the curvature is zero at both ends, splines created this way can be joined at
+    /// waypoints without any jump in curvature.
+    pub fn from_poses(start: Pose2d, end: Pose2d) -> Self {
+        let distance = start.position.distance(end.position);
+        Self::new(
+            start.position,
+            tangent(start, distance),
+            Vec2::ZERO,
+            end.position,
+            tangent(end, distance),
+            Vec2::ZERO,
+        )
+    }
+}
+
+impl Spline for QuinticHermite {
+    fn point(&self, t: f64) -> Vec2 {
+        self.polynomial.point(t)
+    }
+
+    fn derivative(&self, t: f64) -> Vec2 {
+        self.polynomial.derivative(t)
+    }
+
+    fn second_derivative(&self, t: f64) -> Vec2 {
+        self.polynomial.second_derivative(t)
+    }
+}
+
+/// A point on a [`Path`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PathPoint {
+    /// The position on the path, facing along it.
+    pub pose: Pose2d,
+    /// The distance along the path from its start.
+    pub distance: f64,
+    /// The curvature of the path, positive when turning counterclockwise.
+    pub curvature: f64,
+}
+
+/// A path made of splines, sampled at even distances along its length.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    points: Vec<PathPoint>,
+}
+
+impl Path {
+    /// Creates a path through `waypoints` joined by [`CubicBezier`] splines, sampled every
+    /// `spacing` units along the path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than two waypoints or `spacing` isn't positive.
+    pub fn cubic_bezier(waypoints: &[Pose2d], spacing: f64) -> Self {
+        Self::from_splines(Self::segments(waypoints, CubicBezier::from_poses), spacing)
+    }
+
+    /// Creates a path through `waypoints` joined by [`QuinticHermite`] splines, sampled every
+    /// `spacing` units along the path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than two waypoints or `spacing` isn't positive.
+    pub fn quintic_hermite(waypoints: &[Pose2d], spacing: f64) -> Self {
+        Self::from_splines(
+            Self::segments(waypoints, QuinticHermite::from_poses),
+            spacing,
+        )
+    }
+
+    fn segments<S>(waypoints: &[Pose2d], spline: impl Fn(Pose2d, Pose2d) -> S) -> Vec<S> {
+        assert!(waypoints.len() >= 2, "paths need at least two waypoints");
+        waypoints
+            .windows(2)
+            .map(|pair| spline(pair[0], pair[1]))
+            .collect()
+    }
+
+    /// Creates a path following each spline in turn, sampled every `spacing` units along the
+    /// path.
+    ///
+    /// The path always includes the start and end of the splines, so the final two points may
+    /// be closer together than `spacing`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `splines` is empty or `spacing` isn't positive.
+    pub fn from_splines<S: Spline>(splines: impl IntoIterator<Item = S>, spacing: f64) -> Self {
+        assert!(spacing > 0.0, "path spacing must be positive");
+
+        let mut points = Vec::new();
+        let mut length = 0.0;
+        let mut next_distance = 0.0;
+        let mut last = None;
+
+        for spline in splines {
+            let point_at = |t: f64, distance: f64| PathPoint {
+                pose: Pose2d::new(spline.point(t), spline.heading(t)),
+                distance,
+                curvature: spline.curvature(t),
+            };
+
+            // Walk along the spline in small straight steps, adding a point each time another
+            // `spacing` has been covered. The parameter of each point is interpolated between
+            // the steps either side of it.
+            let mut previous = spline.point(0.0);
+            for step in 1..=ARC_LENGTH_STEPS {
+                let t = step as f64 / ARC_LENGTH_STEPS as f64;
+                let current = spline.point(t);
+                let step_length = previous.distance(current);
+
+                while next_distance <= length + step_length {
+                    let fraction = if step_length > 0.0 {
+                        (next_distance - length) / step_length
+                    } else {
+                        0.0
+                    };
+                    let t = (step as f64 - 1.0 + fraction) / ARC_LENGTH_STEPS as f64;
+                    points.push(point_at(t, next_distance));
+                    next_distance += spacing;
+                }
+
+                length += step_length;
+                previous = current;
+            }
+
+            last = Some(point_at(1.0, length));
+        }
+
+        let last = last.expect("paths need at least one spline");
+        if points
+            .last()
+            .map_or(true, |point| last.distance - point.distance > 1e-9)
+        {
+            points.push(last);
+        }
+
+        Self { points }
+    }
+
+    /// Returns the sampled points along the path.
+    pub fn points(&self) -> &[PathPoint] {
+        &self.points
+    }
+
+    /// Returns the total length of the path.
+    pub fn length(&self) -> f64 {
+        self.points.last().map_or(0.0, |point| point.distance)
+    }
+}
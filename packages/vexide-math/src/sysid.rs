@@ -0,0 +1,312 @@
+//! System identification for feedforward models.
+//!
+//! System identification measures the feedforward gains of a mechanism from how it actually
+//! responds to voltage, rather than tuning them by hand. Data is usually collected with two
+//! kinds of tests, each run in both directions with [`Motor::set_voltage`]:
+//!
+//! - A quasistatic test slowly ramps the voltage up, so the mechanism barely accelerates and
+//!   the velocity gain can be measured on its own.
+//! - A dynamic test applies a sudden step in voltage, so the mechanism accelerates quickly and
+//!   the acceleration gain can be measured.
+//!
+//! While each test runs, record a [`SysIdSample`] every few milliseconds with the applied
+//! voltage and the measured position and velocity. Acceleration can be estimated from the
+//! change in velocity between samples; filtering the velocity first, for example with a
+//! [`LowPassFilter`](crate::filter::LowPassFilter), helps here. The samples are then fit by
+//! least squares with [`fit_simple`], [`fit_elevator`] or [`fit_arm`], depending on the
+//! mechanism.
+//!
+//! [`Motor::set_voltage`]: vexide_devices::smart::Motor::set_voltage
+
+use crate::feedforward::{ArmFeedforward, ElevatorFeedforward};
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+
+/// A single measurement of a mechanism's response to voltage.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SysIdSample {
+    /// The voltage applied to the motor.
+    pub voltage: f64,
+    /// The mechanism's velocity.
+    pub velocity: f64,
+    /// The mechanism's acceleration.
+    pub acceleration: f64,
+    /// The mechanism's position. For arms, this is the angle above horizontal in radians.
+    pub position: f64,
+}
+
+/// Feedforward gains fit by system identification.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FeedforwardGains {
+    /// Voltage needed to overcome static friction.
+    pub ks: f64,
+    /// Voltage needed to hold the load up against gravity. Zero for [`fit_simple`].
+    pub kg: f64,
+    /// Voltage needed per unit of velocity.
+    pub kv: f64,
+    /// Voltage needed per unit of acceleration.
+    pub ka: f64,
+    /// How well the model fits the samples, from `0.0` to `1.0`.
+    ///
+    /// Values close to `1.0` mean the model explains the measured voltages well. Low values
+    /// suggest noisy data or a mechanism the model doesn't describe, such as one with a lot of
+    /// backlash.
+    pub r_squared: f64,
+}
+
+impl FeedforwardGains {
+    /// Creates an [`ElevatorFeedforward`] with these gains.
+    pub const fn to_elevator(&self) -> ElevatorFeedforward {
+        ElevatorFeedforward::new(self.ks, self.kg, self.kv, self.ka)
+    }
+
+    /// Creates an [`ArmFeedforward`] with these gains.
+    pub const fn to_arm(&self) -> ArmFeedforward {
+        ArmFeedforward::new(self.ks, self.kg, self.kv, self.ka)
+    }
+}
+
+/// Fits V = Kₛ sign(v) + Kᵥ v + Kₐ a, for mechanisms that aren't affected by gravity such as
+/// drivetrains and flywheels.
+///
+/// Returns `None` if the samples don't contain enough variety to determine every gain, such as
+/// when the mechanism was only ever driven at one speed.
+pub fn fit_simple(samples: impl IntoIterator<Item = SysIdSample>) -> Option<FeedforwardGains> {
+    let ([ks, kv, ka], r_squared) = fit(samples, |sample| {
+        [
+            num::signum(sample.velocity),
+            sample.velocity,
+            sample.acceleration,
+        ]
+    })?;
+
+    Some(FeedforwardGains {
+        ks,
+        kg: 0.0,
+        kv,
+        ka,
+        r_squared,
+    })
+}
+
+/// Fits V = K_g + Kₛ sign(v) + Kᵥ v + Kₐ a, for mechanisms that move a load straight up and
+/// down.
+///
+/// Returns `None` if the samples don't contain enough variety to determine every gain. The
+/// tests must be run in both directions for gravity to be told apart from friction.
+pub fn fit_elevator(samples: impl IntoIterator<Item = SysIdSample>) -> Option<FeedforwardGains> {
+    let ([kg, ks, kv, ka], r_squared) = fit(samples, |sample| {
+        [
+            1.0,
+            num::signum(sample.velocity),
+            sample.velocity,
+            sample.acceleration,
+        ]
+    })?;
+
+    Some(FeedforwardGains {
+        ks,
+        kg,
+        kv,
+        ka,
+        r_squared,
+    })
+}
+
+/// Fits V = K_g cos θ + Kₛ sign(ω) + Kᵥ ω + Kₐ α, for arms rotating around a horizontal pivot.
+///
+/// Returns `None` if the samples don't contain enough variety to determine every gain. The
+/// tests must be run in both directions and over a range of angles for gravity to be told apart
+/// from friction.
+pub fn fit_arm(samples: impl IntoIterator<Item = SysIdSample>) -> Option<FeedforwardGains> {
+    let ([kg, ks, kv, ka], r_squared) = fit(samples, |sample| {
+        [
+            sample.position.cos(),
+            num::signum(sample.velocity),
+            sample.velocity,
+            sample.acceleration,
+        ]
+    })?;
+
+    Some(FeedforwardGains {
+        ks,
+        kg,
+        kv,
+        ka,
+        r_squared,
+    })
+}
+
+/// Finds the coefficients that best predict each sample's voltage from the features returned
+/// by `features`, along with the coefficient of determination of the fit.
+///
+/// Samples where the mechanism is stationary are skipped, since static friction can hold it
+/// still at any voltage below Kₛ.
+fn fit<const N: usize>(
+    samples: impl IntoIterator<Item = SysIdSample>,
+    features: impl Fn(&SysIdSample) -> [f64; N],
+) -> Option<([f64; N], f64)> {
+    // Accumulate the normal equations XᵀX b = Xᵀy one sample at a time, so the samples never
+    // need to be stored.
+    let mut xtx = [[0.0; N]; N];
+    let mut xty = [0.0; N];
+    let mut yty = 0.0;
+    let mut y_sum = 0.0;
+    let mut count = 0;
+
+    for sample in samples {
+        if sample.velocity == 0.0 {
+            continue;
+        }
+
+        let x = features(&sample);
+        let y = sample.voltage;
+        for i in 0..N {
+            for j in 0..N {
+                xtx[i][j] += x[i] * x[j];
+            }
+            xty[i] += x[i] * y;
+        }
+        yty += y * y;
+        y_sum += y;
+        count += 1;
+    }
+
+    if count < N {
+        return None;
+    }
+
+    let coefficients = solve(xtx, xty)?;
+
+    // The residual sum of squares, expanded so it can be found from the accumulated sums.
+    let mut residual = yty;
+    for i in 0..N {
+        residual -= 2.0 * coefficients[i] * xty[i];
+        for j in 0..N {
+            residual += coefficients[i] * xtx[i][j] * coefficients[j];
+        }
+    }
+    let total = yty - y_sum * y_sum / count as f64;
+    let r_squared = if total > 0.0 {
+        (1.0 - residual / total).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+
+    Some((coefficients, r_squared))
+}
+
+/// Solves `a x = b` by Gaussian elimination, returning `None` if `a` is singular.
+fn solve<const N: usize>(mut a: [[f64; N]; N], mut b: [f64; N]) -> Option<[f64; N]> {
+    let scale = a
+        .iter()
+        .flatten()
+        .fold(0.0_f64, |max, value| max.max(value.abs()));
+
+    for column in 0..N {
+        // Swap the row with the largest value in this column into place to reduce rounding
+        // error.
+        let pivot =
+            (column..N).max_by(|&i, &j| a[i][column].abs().total_cmp(&a[j][column].abs()))?;
+        if a[pivot][column].abs() <= scale * 1e-12 {
+            return None;
+        }
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+
+        for row in column + 1..N {
+            let factor = a[row][column] / a[column][column];
+            for k in column..N {
+                a[row][k] -= factor * a[column][k];
+            }
+            b[row] -= factor * b[column];
+        }
+    }
+
+    let mut x = [0.0; N];
+    for row in (0..N).rev() {
+        let known: f64 = (row + 1..N).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - known) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    /// Samples in both directions over a range of speeds, accelerations and angles, with
+    /// voltages from `model`.
+    fn samples(model: impl Fn(&SysIdSample) -> f64) -> Vec<SysIdSample> {
+        (1..=40)
+            .flat_map(|i| {
+                let speed = i as f64 * 0.25;
+                [speed, -speed].map(|velocity| {
+                    let mut sample = SysIdSample {
+                        voltage: 0.0,
+                        velocity,
+                        acceleration: (i % 7) as f64 - 3.0,
+                        position: i as f64 * 0.05 - 1.0,
+                    };
+                    sample.voltage = model(&sample);
+                    sample
+                })
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn recovers_simple_gains() {
+        let gains = fit_simple(samples(|sample| {
+            0.5 * num::signum(sample.velocity) + 1.2 * sample.velocity + 0.3 * sample.acceleration
+        }))
+        .unwrap();
+
+        assert_close(gains.ks, 0.5);
+        assert_close(gains.kg, 0.0);
+        assert_close(gains.kv, 1.2);
+        assert_close(gains.ka, 0.3);
+        assert_close(gains.r_squared, 1.0);
+    }
+
+    #[test]
+    fn recovers_arm_gains() {
+        let gains = fit_arm(samples(|sample| {
+            0.8 * sample.position.cos()
+                + 0.5 * num::signum(sample.velocity)
+                + 1.2 * sample.velocity
+                + 0.3 * sample.acceleration
+        }))
+        .unwrap();
+
+        assert_close(gains.kg, 0.8);
+        assert_close(gains.ks, 0.5);
+        assert_close(gains.kv, 1.2);
+        assert_close(gains.ka, 0.3);
+        assert_close(gains.r_squared, 1.0);
+    }
+
+    #[test]
+    fn single_speed_is_underdetermined() {
+        let samples = || {
+            (0..20).map(|i| SysIdSample {
+                voltage: 6.0,
+                velocity: 4.0,
+                acceleration: 0.0,
+                position: i as f64 * 0.1,
+            })
+        };
+
+        assert_eq!(fit_simple(samples()), None);
+        assert_eq!(fit_arm(samples()), None);
+    }
+}
@@ -0,0 +1,197 @@
+//! Time-parameterized trajectories.
+//!
+//! A trajectory describes where a robot should be, and how fast it should be moving, at every
+//! point in time during a move. Trajectory followers such as
+//! [`RamseteController`](crate::ramsete::RamseteController) sample a trajectory at the time
+//! elapsed since the move started and drive the robot towards the sampled state.
+//!
+//! A [`SampledTrajectory`] can be generated from a [`Path`] by planning how fast the robot can
+//! drive along it without exceeding a set of [`TrajectoryConstraints`].
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+#[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+use crate::prelude::*;
+use crate::{geometry::Pose2d, spline::Path};
+
+/// The desired state of a robot at a point in a trajectory.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TrajectoryState {
+    /// The robot's pose.
+    pub pose: Pose2d,
+    /// The robot's forward velocity.
+    pub linear_velocity: f64,
+    /// The robot's rate of rotation in radians per second, counterclockwise positive.
+    pub angular_velocity: f64,
+}
+
+/// A move that gives the robot's desired state over time.
+pub trait Trajectory {
+    /// Returns the state the robot should be in once `elapsed` has passed since the start of
+    /// the trajectory.
+    ///
+    /// Times past the end of the trajectory return the final state.
+    fn sample(&self, elapsed: Duration) -> TrajectoryState;
+
+    /// Returns the total time taken by the trajectory.
+    fn duration(&self) -> Duration;
+
+    /// Returns `true` if the trajectory is complete once `elapsed` has passed.
+    fn is_finished(&self, elapsed: Duration) -> bool {
+        elapsed >= self.duration()
+    }
+}
+
+/// Limits on how fast a robot can drive along a trajectory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrajectoryConstraints {
+    /// The fastest the robot can drive.
+    pub max_velocity: f64,
+    /// The fastest the robot can speed up or slow down.
+    pub max_acceleration: f64,
+    /// The largest sideways acceleration the robot can take while turning before it slips.
+    ///
+    /// This slows the robot down through tight turns. There is no limit by default.
+    pub max_centripetal_acceleration: f64,
+}
+
+impl TrajectoryConstraints {
+    /// Creates constraints with the given maximum velocity and acceleration.
+    pub const fn new(max_velocity: f64, max_acceleration: f64) -> Self {
+        Self {
+            max_velocity,
+            max_acceleration,
+            max_centripetal_acceleration: f64::INFINITY,
+        }
+    }
+
+    /// Limits the sideways acceleration of the robot while turning.
+    pub const fn with_max_centripetal_acceleration(mut self, acceleration: f64) -> Self {
+        self.max_centripetal_acceleration = acceleration;
+        self
+    }
+
+    /// Returns the fastest the robot can drive through a point with the given curvature.
+    fn velocity_limit(&self, curvature: f64) -> f64 {
+        // Centripetal acceleration is v² κ.
+        let curvature = curvature.abs();
+        if curvature > 0.0 {
+            self.max_velocity
+                .min((self.max_centripetal_acceleration / curvature).sqrt())
+        } else {
+            self.max_velocity
+        }
+    }
+}
+
+/// A trajectory made of states at known times, generated from a [`Path`].
+///
+/// Sampling between two states interpolates between them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampledTrajectory {
+    /// Each state, paired with the number of seconds from the start of the trajectory it occurs
+    /// at.
+    states: Vec<(f64, TrajectoryState)>,
+}
+
+impl SampledTrajectory {
+    /// Plans how fast to drive along `path`, starting and ending at rest, without exceeding
+    /// `constraints`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the maximum velocity or acceleration isn't positive.
+    pub fn new(path: &Path, constraints: TrajectoryConstraints) -> Self {
+        assert!(
+            constraints.max_velocity > 0.0 && constraints.max_acceleration > 0.0,
+            "trajectory constraints must be positive"
+        );
+
+        let points = path.points();
+        let last = points.len() - 1;
+        let mut velocities: Vec<f64> = points
+            .iter()
+            .map(|point| constraints.velocity_limit(point.curvature))
+            .collect();
+        velocities[0] = 0.0;
+        velocities[last] = 0.0;
+
+        // Reaching velocity v from u over distance d at acceleration a requires v² = u² + 2ad.
+        // A forward pass limits how quickly the robot speeds up, and a backward pass limits how
+        // quickly it slows down.
+        let reachable = |from: f64, distance: f64| {
+            (from * from + 2.0 * constraints.max_acceleration * distance).sqrt()
+        };
+        for i in 1..=last {
+            let distance = points[i].distance - points[i - 1].distance;
+            velocities[i] = velocities[i].min(reachable(velocities[i - 1], distance));
+        }
+        for i in (0..last).rev() {
+            let distance = points[i + 1].distance - points[i].distance;
+            velocities[i] = velocities[i].min(reachable(velocities[i + 1], distance));
+        }
+
+        let mut time = 0.0;
+        let mut states = Vec::with_capacity(points.len());
+        for (i, (point, &velocity)) in points.iter().zip(&velocities).enumerate() {
+            if i > 0 {
+                // Acceleration is constant between points, so the average velocity is the mean
+                // of the two ends.
+                let distance = point.distance - points[i - 1].distance;
+                let average_velocity = (velocity + velocities[i - 1]) / 2.0;
+                if average_velocity > 0.0 {
+                    time += distance / average_velocity;
+                }
+            }
+
+            states.push((
+                time,
+                TrajectoryState {
+                    pose: point.pose,
+                    linear_velocity: velocity,
+                    angular_velocity: velocity * point.curvature,
+                },
+            ));
+        }
+
+        Self { states }
+    }
+
+    /// Returns each state in the trajectory, paired with the time it occurs at.
+    pub fn states(&self) -> impl Iterator<Item = (Duration, TrajectoryState)> + '_ {
+        self.states
+            .iter()
+            .map(|&(time, state)| (Duration::from_secs_f64(time), state))
+    }
+}
+
+impl Trajectory for SampledTrajectory {
+    fn sample(&self, elapsed: Duration) -> TrajectoryState {
+        let time = elapsed.as_secs_f64();
+        let next = self
+            .states
+            .partition_point(|&(state_time, _)| state_time <= time);
+
+        if next == 0 {
+            return self.states[0].1;
+        }
+        let (previous_time, previous) = self.states[next - 1];
+        let Some(&(next_time, next)) = self.states.get(next) else {
+            return previous;
+        };
+
+        let t = (time - previous_time) / (next_time - previous_time);
+        TrajectoryState {
+            pose: previous.pose.interpolate(next.pose, t),
+            linear_velocity: previous.linear_velocity
+                + (next.linear_velocity - previous.linear_velocity) * t,
+            angular_velocity: previous.angular_velocity
+                + (next.angular_velocity - previous.angular_velocity) * t,
+        }
+    }
+
+    fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.states[self.states.len() - 1].0)
+    }
+}
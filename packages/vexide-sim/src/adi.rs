@@ -0,0 +1,18 @@
+//! Simulated ADI (three wire) ports.
+
+use vex_sdk::V5_AdiPortConfiguration;
+
+/// The number of ADI ports on an expander or on the brain.
+pub const ADI_PORT_COUNT: usize = 8;
+
+/// State of a simulated V5 three wire expander, or of the brain's onboard ADI ports.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SimAdiExpander {
+    /// The configuration of each port.
+    pub configs: [V5_AdiPortConfiguration; ADI_PORT_COUNT],
+    /// The value of each port.
+    ///
+    /// For inputs, this should be set by tests. For outputs, this holds the last value written
+    /// by vexide.
+    pub values: [i32; ADI_PORT_COUNT],
+}
@@ -0,0 +1,298 @@
+//! The simulated V5 brain.
+//!
+//! All simulated state lives in a [`Brain`], which is stored per-thread. Tests access it through
+//! [`with_brain`], or through the shorthand functions re-exported from the crate root.
+
+use std::{boxed::Box, cell::RefCell, collections::VecDeque, vec::Vec};
+
+use vex_sdk::{V5_Device, V5_DeviceT, V5_DeviceType, V5_TouchStatus};
+
+use crate::{
+    adi::SimAdiExpander,
+    controller::SimController,
+    device::{SimDevice, SimSmartDevice},
+    display::SimDisplay,
+    time::Clock,
+};
+
+/// The number of entries in the VEXos device table.
+pub const DEVICE_TABLE_SIZE: usize = 32;
+
+/// The smart port index that VEXos uses to represent the brain's onboard ADI ports.
+pub const INTERNAL_ADI_PORT: u8 = 22;
+
+/// State of the brain's battery, as reported by the `vexBattery*` functions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimBattery {
+    /// Battery voltage in millivolts.
+    pub voltage: i32,
+    /// Current drawn from the battery in milliamps.
+    pub current: i32,
+    /// Battery temperature in degrees Celsius.
+    pub temperature: f64,
+    /// Remaining battery capacity as a percentage.
+    pub capacity: f64,
+}
+
+impl Default for SimBattery {
+    fn default() -> Self {
+        Self {
+            voltage: 12_800,
+            current: 0,
+            temperature: 25.0,
+            capacity: 100.0,
+        }
+    }
+}
+
+/// A simulated V5 brain.
+#[derive(Debug)]
+pub struct Brain {
+    /// The raw device table returned by `vexDeviceGetByIndex`.
+    ///
+    /// This is boxed so that device handles handed out to vexide remain valid for the lifetime
+    /// of the thread.
+    device_table: Box<[V5_Device; DEVICE_TABLE_SIZE]>,
+    devices: [Option<SimDevice>; DEVICE_TABLE_SIZE],
+
+    /// Raw competition status bits returned by `vexCompetitionStatus`.
+    pub competition_status: u32,
+    /// The primary and partner controllers.
+    pub controllers: [SimController; 2],
+    /// The brain's battery.
+    pub battery: SimBattery,
+    /// The brain's display.
+    pub display: SimDisplay,
+    /// The most recent touch event on the display.
+    pub touch: V5_TouchStatus,
+    /// Bytes written to the serial terminal (stdout).
+    pub stdout: Vec<u8>,
+    /// Bytes waiting to be read from the serial terminal (stdin).
+    pub stdin: VecDeque<u8>,
+    /// Whether an SD card is inserted.
+    pub sd_card_inserted: bool,
+    /// Whether the program has requested to exit with `vexSystemExitRequest`.
+    pub exit_requested: bool,
+    /// The brain's system clock.
+    pub clock: Clock,
+}
+
+impl Brain {
+    /// Creates a brain with nothing plugged into its smart ports.
+    pub fn new() -> Self {
+        let mut device_table = Box::new([V5_Device::default(); DEVICE_TABLE_SIZE]);
+        for (index, device) in device_table.iter_mut().enumerate() {
+            device.zero_indexed_port = index as u8;
+            device.one_indexed_port = index as u8 + 1;
+        }
+
+        let mut brain = Self {
+            device_table,
+            devices: Default::default(),
+            competition_status: 0,
+            controllers: [SimController::default(), SimController::default()],
+            battery: SimBattery::default(),
+            display: SimDisplay::default(),
+            touch: V5_TouchStatus::default(),
+            stdout: Vec::new(),
+            stdin: VecDeque::new(),
+            sd_card_inserted: false,
+            exit_requested: false,
+            clock: Clock::new(),
+        };
+
+        // VEXos reports the onboard ADI ports as an ADI expander on a virtual port.
+        brain.connect(INTERNAL_ADI_PORT, SimAdiExpander::default());
+
+        brain
+    }
+
+    /// Plugs a device into the given smart port, replacing anything already connected to it.
+    ///
+    /// Ports are indexed starting from 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the port is outside of the device table.
+    pub fn connect(&mut self, port: u8, device: impl Into<SimDevice>) {
+        let device = device.into();
+        let index = Self::table_index(port);
+
+        self.device_table[index].device_type = device.device_type();
+        self.device_table[index].installed = true;
+        self.devices[index] = Some(device);
+    }
+
+    /// Unplugs the device connected to the given smart port, returning it.
+    ///
+    /// Ports are indexed starting from 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the port is outside of the device table.
+    pub fn disconnect(&mut self, port: u8) -> Option<SimDevice> {
+        let index = Self::table_index(port);
+
+        self.device_table[index].device_type = V5_DeviceType::kDeviceTypeNoSensor;
+        self.device_table[index].installed = false;
+        self.devices[index].take()
+    }
+
+    /// Gets the device connected to the given smart port.
+    pub fn device(&self, port: u8) -> Option<&SimDevice> {
+        self.devices.get(port.checked_sub(1)? as usize)?.as_ref()
+    }
+
+    /// Gets the device connected to the given smart port mutably.
+    pub fn device_mut(&mut self, port: u8) -> Option<&mut SimDevice> {
+        self.devices
+            .get_mut(port.checked_sub(1)? as usize)?
+            .as_mut()
+    }
+
+    /// Gets the device of type `T` connected to the given smart port, or `None` if
+    /// the port is empty or holds another type of device.
+    pub fn get<T: SimSmartDevice>(&self, port: u8) -> Option<&T> {
+        T::from_device(self.device(port)?)
+    }
+
+    /// Gets the device of type `T` connected to the given smart port mutably, or `None`
+    /// if the port is empty or holds another type of device.
+    pub fn get_mut<T: SimSmartDevice>(&mut self, port: u8) -> Option<&mut T> {
+        T::from_device_mut(self.device_mut(port)?)
+    }
+
+    /// Returns a raw handle to an entry of the device table.
+    pub(crate) fn device_handle(&mut self, index: u32) -> V5_DeviceT {
+        &mut self.device_table[index as usize]
+    }
+
+    fn table_index(port: u8) -> usize {
+        assert!(
+            (1..=DEVICE_TABLE_SIZE as u8).contains(&port),
+            "port {port} is outside of the device table"
+        );
+        (port - 1) as usize
+    }
+}
+
+impl Default for Brain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+std::thread_local! {
+    static BRAIN: RefCell<Brain> = RefCell::new(Brain::new());
+}
+
+/// Runs a closure with the current thread's simulated brain.
+///
+/// # Panics
+///
+/// Panics if called from inside another call to this function, including by calling into
+/// vexide from within the closure.
+pub fn with_brain<R>(f: impl FnOnce(&mut Brain) -> R) -> R {
+    BRAIN.with(|brain| f(&mut brain.borrow_mut()))
+}
+
+/// Resets the current thread's simulated brain to its initial state.
+pub fn reset() {
+    with_brain(|brain| {
+        let mut fresh = Brain::new();
+
+        // Keep the original device table allocation, since vexide may still be holding
+        // handles into it.
+        *brain.device_table = *fresh.device_table;
+        core::mem::swap(&mut brain.device_table, &mut fresh.device_table);
+
+        *brain = fresh;
+    });
+}
+
+/// Plugs a device into the given smart port of the current thread's brain.
+///
+/// See [`Brain::connect`].
+pub fn connect(port: u8, device: impl Into<SimDevice>) {
+    with_brain(|brain| brain.connect(port, device));
+}
+
+/// Unplugs the device connected to the given smart port of the current thread's brain.
+///
+/// See [`Brain::disconnect`].
+pub fn disconnect(port: u8) -> Option<SimDevice> {
+    with_brain(|brain| brain.disconnect(port))
+}
+
+/// Runs a closure with the device of type `T` connected to the given smart port of the
+/// current thread's brain, returning `None` if there is no such device.
+pub fn with_device<T: SimSmartDevice, R>(port: u8, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+    with_brain(|brain| brain.get_mut(port).map(f))
+}
+
+#[cfg(test)]
+mod tests {
+    use vex_sdk::V5_DeviceType;
+
+    use super::*;
+    use crate::{
+        distance::SimDistanceSensor, motor::SimMotor, vexDeviceGetByIndex, vexDevicesGetNumber,
+        vexDevicesGetNumberByType,
+    };
+
+    #[test]
+    fn connect_updates_device_table() {
+        reset();
+        connect(3, SimMotor::default());
+
+        let handle = unsafe { vexDeviceGetByIndex(2) };
+        let entry = unsafe { *handle };
+        assert!(entry.installed);
+        assert_eq!(entry.one_indexed_port, 3);
+        assert_eq!(entry.device_type, V5_DeviceType::kDeviceTypeMotorSensor);
+
+        assert!(matches!(disconnect(3), Some(SimDevice::Motor(_))));
+        let entry = unsafe { *handle };
+        assert!(!entry.installed);
+        assert_eq!(entry.device_type, V5_DeviceType::kDeviceTypeNoSensor);
+    }
+
+    #[test]
+    fn devices_are_counted_by_type() {
+        reset();
+        connect(1, SimMotor::default());
+        connect(2, SimMotor::default());
+        connect(5, SimDistanceSensor::default());
+
+        // The onboard ADI ports are always reported as a device.
+        assert_eq!(unsafe { vexDevicesGetNumber() }, 4);
+        assert_eq!(
+            unsafe { vexDevicesGetNumberByType(V5_DeviceType::kDeviceTypeMotorSensor) },
+            2
+        );
+    }
+
+    #[test]
+    fn typed_access_checks_device_type() {
+        reset();
+        connect(1, SimMotor::default());
+
+        assert!(with_brain(|brain| brain.get::<SimMotor>(1).is_some()));
+        assert!(with_brain(|brain| brain
+            .get::<SimDistanceSensor>(1)
+            .is_none()));
+        assert_eq!(with_device(2, |motor: &mut SimMotor| motor.velocity), None);
+    }
+
+    #[test]
+    fn reset_keeps_device_handles_valid() {
+        reset();
+        connect(1, SimMotor::default());
+        let handle = unsafe { vexDeviceGetByIndex(0) };
+
+        reset();
+        assert!(!unsafe { *handle }.installed);
+        connect(1, SimMotor::default());
+        assert!(unsafe { *handle }.installed);
+    }
+}
@@ -0,0 +1,48 @@
+//! Simulated V5 controllers.
+
+use std::string::String;
+
+use vex_sdk::{V5_ControllerIndex, V5_ControllerStatus};
+
+/// The number of data channels readable with `vexControllerGet`.
+pub const CHANNEL_COUNT: usize = 21;
+
+/// State of a simulated V5 controller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimController {
+    /// How the controller is connected to the brain.
+    pub connection: V5_ControllerStatus,
+    /// Raw values of each data channel, indexed by [`V5_ControllerIndex`].
+    pub channels: [i32; CHANNEL_COUNT],
+    /// Text displayed on each line of the controller screen.
+    pub lines: [String; 3],
+}
+
+impl SimController {
+    /// Get the raw value of a data channel.
+    pub fn get(&self, index: V5_ControllerIndex) -> i32 {
+        self.channels
+            .get(index.0 as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Set the raw value of a data channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not a valid data channel.
+    pub fn set(&mut self, index: V5_ControllerIndex, value: i32) {
+        self.channels[index.0 as usize] = value;
+    }
+}
+
+impl Default for SimController {
+    fn default() -> Self {
+        Self {
+            connection: V5_ControllerStatus::kV5ControllerOffline,
+            channels: [0; CHANNEL_COUNT],
+            lines: Default::default(),
+        }
+    }
+}
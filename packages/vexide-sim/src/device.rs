@@ -0,0 +1,105 @@
+//! Simulated smart port devices.
+
+use vex_sdk::V5_DeviceType;
+
+use crate::{
+    adi::SimAdiExpander,
+    distance::SimDistanceSensor,
+    imu::SimInertialSensor,
+    motor::SimMotor,
+    optical::SimOpticalSensor,
+    rotation::SimRotationSensor,
+    serial::{SimRadio, SimSerialPort},
+    vision::SimVisionSensor,
+};
+
+/// A device that can be plugged into a smart port of the simulated brain.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimDevice {
+    /// A V5 smart motor.
+    Motor(SimMotor),
+    /// A V5 rotation sensor.
+    Rotation(SimRotationSensor),
+    /// A V5 inertial sensor.
+    Imu(SimInertialSensor),
+    /// A V5 distance sensor.
+    Distance(SimDistanceSensor),
+    /// A V5 optical sensor.
+    Optical(SimOpticalSensor),
+    /// A V5 vision sensor.
+    Vision(SimVisionSensor),
+    /// A V5 three wire expander, or the brain's onboard ADI ports.
+    Adi(SimAdiExpander),
+    /// A smart port configured for generic serial communication.
+    Serial(SimSerialPort),
+    /// A V5 radio used for VEXlink.
+    Radio(SimRadio),
+}
+
+impl SimDevice {
+    /// Get the raw device type that VEXos reports for this device.
+    pub const fn device_type(&self) -> V5_DeviceType {
+        match self {
+            Self::Motor(_) => V5_DeviceType::kDeviceTypeMotorSensor,
+            Self::Rotation(_) => V5_DeviceType::kDeviceTypeAbsEncSensor,
+            Self::Imu(_) => V5_DeviceType::kDeviceTypeImuSensor,
+            Self::Distance(_) => V5_DeviceType::kDeviceTypeDistanceSensor,
+            Self::Optical(_) => V5_DeviceType::kDeviceTypeOpticalSensor,
+            Self::Vision(_) => V5_DeviceType::kDeviceTypeVisionSensor,
+            Self::Adi(_) => V5_DeviceType::kDeviceTypeAdiSensor,
+            Self::Serial(_) => V5_DeviceType::kDeviceTypeGenericSerial,
+            Self::Radio(_) => V5_DeviceType::kDeviceTypeRadioSensor,
+        }
+    }
+}
+
+/// Common functionality for the state of each simulated smart device type.
+pub trait SimSmartDevice: Into<SimDevice> {
+    /// Borrow the state of this device type from a [`SimDevice`], or `None` if the device is
+    /// of another type.
+    fn from_device(device: &SimDevice) -> Option<&Self>;
+
+    /// Mutably borrow the state of this device type from a [`SimDevice`], or `None` if the
+    /// device is of another type.
+    fn from_device_mut(device: &mut SimDevice) -> Option<&mut Self>;
+}
+
+macro_rules! impl_sim_smart_device {
+    ($($variant:ident => $ty:ty),+ $(,)?) => {
+        $(
+            impl From<$ty> for SimDevice {
+                fn from(value: $ty) -> Self {
+                    Self::$variant(value)
+                }
+            }
+
+            impl SimSmartDevice for $ty {
+                fn from_device(device: &SimDevice) -> Option<&Self> {
+                    match device {
+                        SimDevice::$variant(inner) => Some(inner),
+                        _ => None,
+                    }
+                }
+
+                fn from_device_mut(device: &mut SimDevice) -> Option<&mut Self> {
+                    match device {
+                        SimDevice::$variant(inner) => Some(inner),
+                        _ => None,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_sim_smart_device! {
+    Motor => SimMotor,
+    Rotation => SimRotationSensor,
+    Imu => SimInertialSensor,
+    Distance => SimDistanceSensor,
+    Optical => SimOpticalSensor,
+    Vision => SimVisionSensor,
+    Adi => SimAdiExpander,
+    Serial => SimSerialPort,
+    Radio => SimRadio,
+}
@@ -0,0 +1,93 @@
+//! The simulated brain display.
+
+use std::{string::String, vec::Vec};
+
+/// How text was drawn to the display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextKind {
+    /// Small text drawn at a point.
+    Small,
+    /// Medium text.
+    Medium,
+    /// Large text.
+    Big,
+    /// Medium text centered on a line.
+    Centered,
+    /// Large text centered on a line.
+    BigCentered,
+}
+
+/// Where text was drawn on the display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextPosition {
+    /// A pixel coordinate.
+    Point(i32, i32),
+    /// A line number.
+    Line(i32),
+}
+
+/// A drawing operation performed on the display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DisplayCommand {
+    /// The display was cleared to the background color.
+    Erase,
+    /// A region of the display was scrolled.
+    Scroll {
+        /// The region that was scrolled.
+        rect: (i32, i32, i32, i32),
+        /// The number of lines scrolled.
+        lines: i32,
+    },
+    /// A buffer was copied to a region of the display.
+    CopyRect((i32, i32, i32, i32)),
+    /// A single pixel was set.
+    Pixel(u32, u32),
+    /// A line was drawn.
+    Line((i32, i32, i32, i32)),
+    /// A rectangle was outlined.
+    RectDraw((i32, i32, i32, i32)),
+    /// A rectangle was filled.
+    RectFill((i32, i32, i32, i32)),
+    /// A circle was outlined.
+    CircleDraw {
+        /// The center of the circle.
+        center: (i32, i32),
+        /// The radius of the circle.
+        radius: i32,
+    },
+    /// A circle was filled.
+    CircleFill {
+        /// The center of the circle.
+        center: (i32, i32),
+        /// The radius of the circle.
+        radius: i32,
+    },
+    /// Text was drawn.
+    Text {
+        /// Where the text was drawn.
+        position: TextPosition,
+        /// How the text was drawn.
+        kind: TextKind,
+        /// The text that was drawn.
+        text: String,
+    },
+}
+
+/// State of the simulated brain display.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SimDisplay {
+    /// The current foreground color.
+    pub foreground: u32,
+    /// The current background color.
+    pub background: u32,
+    /// Every drawing operation performed on the display, along with the foreground color at
+    /// the time, in the order they were performed.
+    pub commands: Vec<(DisplayCommand, u32)>,
+}
+
+impl SimDisplay {
+    /// Records a drawing operation with the current foreground color.
+    pub(crate) fn push(&mut self, command: DisplayCommand) {
+        self.commands.push((command, self.foreground));
+    }
+}
@@ -0,0 +1,30 @@
+//! Simulated V5 distance sensors.
+
+/// State of a simulated V5 distance sensor.
+///
+/// All values are stored in the raw units used by the SDK.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimDistanceSensor {
+    /// Distance to the detected object in millimeters.
+    pub distance: u32,
+    /// Confidence in the distance reading from 0 to 63.
+    pub confidence: u32,
+    /// Status code.
+    pub status: u32,
+    /// Guess at the relative size of the object.
+    pub object_size: i32,
+    /// Velocity of the object in meters per second.
+    pub object_velocity: f64,
+}
+
+impl Default for SimDistanceSensor {
+    fn default() -> Self {
+        Self {
+            distance: 9999,
+            confidence: 0,
+            status: 0x82,
+            object_size: -1,
+            object_velocity: 0.0,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Simulated V5 inertial sensors.
+
+use vex_sdk::{V5_DeviceImuAttitude, V5_DeviceImuQuaternion, V5_DeviceImuRaw};
+
+/// State of a simulated V5 inertial sensor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimInertialSensor {
+    /// Status flags.
+    ///
+    /// The calibrating bit is managed automatically after a calibration is requested.
+    pub status: u32,
+    /// Unbounded yaw rotation in degrees.
+    pub heading: f64,
+    /// Yaw rotation in degrees bounded to [0, 360).
+    pub degrees: f64,
+    /// Orientation quaternion.
+    pub quaternion: V5_DeviceImuQuaternion,
+    /// Orientation as Euler angles in degrees.
+    pub attitude: V5_DeviceImuAttitude,
+    /// Raw gyroscope rates.
+    pub gyro: V5_DeviceImuRaw,
+    /// Raw accelerometer readings.
+    pub accel: V5_DeviceImuRaw,
+    /// The configured data rate in milliseconds.
+    pub data_rate: u32,
+    /// The number of times a calibration has been requested.
+    pub calibrations: u32,
+    /// How many status reads report the sensor as calibrating after a calibration is requested.
+    pub calibration_reads: u32,
+    calibration_reads_remaining: u32,
+}
+
+impl SimInertialSensor {
+    /// The status bit set while the sensor is calibrating.
+    pub const CALIBRATING: u32 = 0x01;
+
+    /// Requests that the sensor calibrate.
+    pub(crate) fn start_calibration(&mut self) {
+        self.calibrations += 1;
+        self.calibration_reads_remaining = self.calibration_reads;
+    }
+
+    /// Reads the status flags, advancing any in-progress calibration.
+    pub(crate) fn read_status(&mut self) -> u32 {
+        if self.calibration_reads_remaining > 0 {
+            self.calibration_reads_remaining -= 1;
+            self.status | Self::CALIBRATING
+        } else {
+            self.status & !Self::CALIBRATING
+        }
+    }
+}
+
+impl Default for SimInertialSensor {
+    fn default() -> Self {
+        Self {
+            status: 0,
+            heading: 0.0,
+            degrees: 0.0,
+            quaternion: V5_DeviceImuQuaternion {
+                d: 1.0,
+                ..Default::default()
+            },
+            attitude: V5_DeviceImuAttitude::default(),
+            gyro: V5_DeviceImuRaw::default(),
+            accel: V5_DeviceImuRaw {
+                z: 1.0,
+                ..Default::default()
+            },
+            data_rate: 10,
+            calibrations: 0,
+            calibration_reads: 2,
+            calibration_reads_remaining: 0,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Host-side mock of the VEX SDK for [`vexide`](https://crates.io/crates/vexide).
+//!
+//! Every vexide device calls directly into the VEXos user jump table through [`vex_sdk`],
+//! which only exists on a real V5 brain. When vexide is built for a host target (anything
+//! other than the brain or the WASM simulator), the vexide crates that call into the SDK link
+//! against this crate instead. It provides in-process fake implementations of every SDK
+//! function used by vexide, backed by a simulated [`Brain`].
+//!
+//! This allows robot code to be unit-tested with `cargo test` on a development machine
+//! against the real device APIs.
+//!
+//! Crates that call into the SDK depend on `vex-sdk` when built for the brain or the WASM
+//! simulator and on this crate everywhere else, and alias it for host builds so the same
+//! `vex_sdk` paths resolve on every target:
+//!
+//! ```ignore
+//! #[cfg(not(any(target_arch = "arm", target_arch = "wasm32")))]
+//! extern crate vexide_sim as vex_sdk;
+//! ```
+//!
+//! # Scripting the brain
+//!
+//! Tests plug devices into the simulated brain, set their sensor readings and inspect the
+//! commands that vexide sent to them:
+//!
+//! ```
+//! use vexide_devices::smart::{motor::{Direction, Gearset, Motor}, SmartPort};
+//! use vexide_sim::{motor::{MotorCommand, SimMotor}, with_device};
+//!
+//! vexide_sim::connect(1, SimMotor::default());
+//!
+//! let mut motor = Motor::new(unsafe { SmartPort::new(1) }, Gearset::Green, Direction::Forward).unwrap();
+//! motor.set_voltage(6.0).unwrap();
+//!
+//! assert_eq!(
+//!     with_device(1, |motor: &mut SimMotor| motor.command),
+//!     Some(MotorCommand::Voltage(6000))
+//! );
+//! ```
+//!
+//! # Isolation
+//!
+//! Each thread has its own simulated brain, so tests run by the default multithreaded test
+//! harness do not interfere with each other. Note that the vexide async executor is shared
+//! between all threads, so tests that spawn tasks which read devices should be run with
+//! `--test-threads=1`.
+//!
+//! # Compiling for the host
+//!
+//! This repository is configured to build for the V5 brain by default. To run tests on the
+//! host, the target and standard library must be overridden, for example:
+//!
+//! ```sh
+//! cargo test --target x86_64-unknown-linux-gnu -Zbuild-std=std,panic_unwind
+//! ```
+
+#![no_std]
+#![cfg(not(any(target_arch = "arm", target_arch = "wasm32")))]
+#![feature(c_variadic)]
+
+extern crate std;
+
+pub mod adi;
+pub mod brain;
+pub mod controller;
+pub mod device;
+pub mod display;
+pub mod distance;
+pub mod imu;
+pub mod motor;
+pub mod optical;
+pub mod rotation;
+mod sdk;
+pub mod serial;
+pub mod time;
+pub mod vision;
+
+pub use brain::{connect, disconnect, reset, with_brain, with_device, Brain};
+pub use device::{SimDevice, SimSmartDevice};
+pub use sdk::*;
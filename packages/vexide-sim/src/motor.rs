@@ -0,0 +1,105 @@
+//! Simulated V5 smart motors.
+
+use vex_sdk::{V5MotorBrakeMode, V5MotorEncoderUnits, V5MotorGearset, V5_DeviceMotorPid};
+
+/// The last control command sent to a [`SimMotor`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MotorCommand {
+    /// Raw output voltage in millivolts, set with `vexDeviceMotorVoltageSet`.
+    Voltage(i32),
+    /// Velocity setpoint in RPM, set with `vexDeviceMotorVelocitySet`.
+    Velocity(i32),
+    /// Absolute position target, set with `vexDeviceMotorAbsoluteTargetSet`.
+    AbsoluteTarget {
+        /// Target position in the motor's encoder units.
+        position: f64,
+        /// Maximum velocity of the movement in RPM.
+        velocity: i32,
+    },
+}
+
+/// State of a simulated V5 smart motor.
+///
+/// All values are stored in the raw units used by the SDK. Configuration fields are written by
+/// vexide and can be inspected by tests, while telemetry fields are only ever read by vexide and
+/// should be set by tests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimMotor {
+    /// The last control command sent to the motor.
+    pub command: MotorCommand,
+    /// The configured brake mode.
+    pub brake_mode: V5MotorBrakeMode,
+    /// The configured gearset.
+    pub gearset: V5MotorGearset,
+    /// Whether the motor is configured to spin in reverse.
+    pub reversed: bool,
+    /// The configured encoder units.
+    pub encoder_units: V5MotorEncoderUnits,
+    /// The configured current limit in milliamps.
+    pub current_limit: i32,
+    /// The configured voltage limit in millivolts.
+    pub voltage_limit: i32,
+    /// Internal position controller constants, if they have been changed.
+    pub position_pid: Option<V5_DeviceMotorPid>,
+    /// Internal velocity controller constants, if they have been changed.
+    pub velocity_pid: Option<V5_DeviceMotorPid>,
+
+    /// Position in the motor's encoder units.
+    pub position: f64,
+    /// Raw IME tick count.
+    pub raw_position: i32,
+    /// Timestamp of the last raw position reading in milliseconds. If `None`, the brain's clock
+    /// is used.
+    pub raw_position_timestamp: Option<u32>,
+    /// Velocity in RPM.
+    pub velocity: i32,
+    /// Measured voltage in millivolts.
+    pub voltage: i32,
+    /// Current draw in milliamps.
+    pub current: i32,
+    /// Power draw in watts.
+    pub power: f64,
+    /// Torque output in newton-meters.
+    pub torque: f64,
+    /// Efficiency as a percentage.
+    pub efficiency: f64,
+    /// Temperature in degrees Celsius.
+    pub temperature: f64,
+    /// Fault flags.
+    pub faults: u32,
+    /// Status flags.
+    pub flags: u32,
+}
+
+impl SimMotor {
+    /// The default current limit of a V5 smart motor in milliamps.
+    pub const DEFAULT_CURRENT_LIMIT: i32 = 2500;
+}
+
+impl Default for SimMotor {
+    fn default() -> Self {
+        Self {
+            command: MotorCommand::Voltage(0),
+            brake_mode: V5MotorBrakeMode::kV5MotorBrakeModeCoast,
+            gearset: V5MotorGearset::kMotorGearSet_18,
+            reversed: false,
+            encoder_units: V5MotorEncoderUnits::kMotorEncoderDegrees,
+            current_limit: Self::DEFAULT_CURRENT_LIMIT,
+            voltage_limit: 0,
+            position_pid: None,
+            velocity_pid: None,
+            position: 0.0,
+            raw_position: 0,
+            raw_position_timestamp: None,
+            velocity: 0,
+            voltage: 0,
+            current: 0,
+            power: 0.0,
+            torque: 0.0,
+            efficiency: 0.0,
+            temperature: 25.0,
+            faults: 0,
+            flags: 0,
+        }
+    }
+}
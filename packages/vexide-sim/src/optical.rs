@@ -0,0 +1,32 @@
+//! Simulated V5 optical sensors.
+
+use vex_sdk::{V5_DeviceOpticalGesture, V5_DeviceOpticalRaw, V5_DeviceOpticalRgb};
+
+/// State of a simulated V5 optical sensor.
+///
+/// All values are stored in the raw units used by the SDK.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SimOpticalSensor {
+    /// Detected hue in degrees.
+    pub hue: f64,
+    /// Detected saturation from 0 to 1.
+    pub saturation: f64,
+    /// Detected brightness from 0 to 1.
+    pub brightness: f64,
+    /// Proximity reading from 0 to 255.
+    pub proximity: i32,
+    /// Processed RGB reading.
+    pub rgb: V5_DeviceOpticalRgb,
+    /// Raw color channel readings.
+    pub raw: V5_DeviceOpticalRaw,
+    /// The last detected gesture.
+    pub gesture: V5_DeviceOpticalGesture,
+    /// Whether gesture detection is enabled.
+    pub gestures_enabled: bool,
+    /// LED brightness as a percentage.
+    pub led_pwm: i32,
+    /// Integration time in milliseconds.
+    pub integration_time: f64,
+    /// Status flags.
+    pub status: u32,
+}
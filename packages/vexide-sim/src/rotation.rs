@@ -0,0 +1,18 @@
+//! Simulated V5 rotation sensors.
+
+/// State of a simulated V5 rotation sensor.
+///
+/// All values are stored in the raw units used by the SDK.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SimRotationSensor {
+    /// Total rotation in centidegrees.
+    pub position: i32,
+    /// Absolute angle in centidegrees, bounded to [0, 36000).
+    pub angle: i32,
+    /// Velocity in millidegrees per second.
+    pub velocity: i32,
+    /// Whether the sensor is configured to be reversed.
+    pub reversed: bool,
+    /// Status flags.
+    pub status: u32,
+}
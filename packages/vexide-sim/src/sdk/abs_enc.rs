@@ -0,0 +1,41 @@
+pub use vex_sdk::abs_enc::*;
+use vex_sdk::V5_DeviceT;
+
+use super::with_handle;
+use crate::rotation::SimRotationSensor;
+
+pub unsafe extern "C" fn vexDeviceAbsEncReset(device: V5_DeviceT) {
+    with_handle(device, |sensor: &mut SimRotationSensor| sensor.position = 0)
+}
+
+pub unsafe extern "C" fn vexDeviceAbsEncPositionSet(device: V5_DeviceT, position: i32) {
+    with_handle(device, |sensor: &mut SimRotationSensor| {
+        sensor.position = position
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceAbsEncPositionGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |sensor: &mut SimRotationSensor| sensor.position)
+}
+
+pub unsafe extern "C" fn vexDeviceAbsEncVelocityGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |sensor: &mut SimRotationSensor| sensor.velocity)
+}
+
+pub unsafe extern "C" fn vexDeviceAbsEncAngleGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |sensor: &mut SimRotationSensor| sensor.angle)
+}
+
+pub unsafe extern "C" fn vexDeviceAbsEncReverseFlagSet(device: V5_DeviceT, value: bool) {
+    with_handle(device, |sensor: &mut SimRotationSensor| {
+        sensor.reversed = value
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceAbsEncReverseFlagGet(device: V5_DeviceT) -> bool {
+    with_handle(device, |sensor: &mut SimRotationSensor| sensor.reversed)
+}
+
+pub unsafe extern "C" fn vexDeviceAbsEncStatusGet(device: V5_DeviceT) -> u32 {
+    with_handle(device, |sensor: &mut SimRotationSensor| sensor.status)
+}
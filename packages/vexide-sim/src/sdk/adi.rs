@@ -0,0 +1,40 @@
+pub use vex_sdk::adi::*;
+use vex_sdk::V5_DeviceT;
+
+use super::with_handle;
+use crate::adi::SimAdiExpander;
+
+pub unsafe extern "C" fn vexDeviceAdiPortConfigSet(
+    device: V5_DeviceT,
+    port: u32,
+    config: V5_AdiPortConfiguration,
+) {
+    with_handle(device, |adi: &mut SimAdiExpander| {
+        if let Some(slot) = adi.configs.get_mut(port as usize) {
+            *slot = config;
+        }
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceAdiPortConfigGet(
+    device: V5_DeviceT,
+    port: u32,
+) -> V5_AdiPortConfiguration {
+    with_handle(device, |adi: &mut SimAdiExpander| {
+        adi.configs.get(port as usize).copied().unwrap_or_default()
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceAdiValueSet(device: V5_DeviceT, port: u32, value: i32) {
+    with_handle(device, |adi: &mut SimAdiExpander| {
+        if let Some(slot) = adi.values.get_mut(port as usize) {
+            *slot = value;
+        }
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceAdiValueGet(device: V5_DeviceT, port: u32) -> i32 {
+    with_handle(device, |adi: &mut SimAdiExpander| {
+        adi.values.get(port as usize).copied().unwrap_or_default()
+    })
+}
@@ -0,0 +1,19 @@
+use core::ffi::c_double;
+
+use crate::brain::with_brain;
+
+pub unsafe extern "C" fn vexBatteryVoltageGet() -> i32 {
+    with_brain(|brain| brain.battery.voltage)
+}
+
+pub unsafe extern "C" fn vexBatteryCurrentGet() -> i32 {
+    with_brain(|brain| brain.battery.current)
+}
+
+pub unsafe extern "C" fn vexBatteryTemperatureGet() -> c_double {
+    with_brain(|brain| brain.battery.temperature)
+}
+
+pub unsafe extern "C" fn vexBatteryCapacityGet() -> c_double {
+    with_brain(|brain| brain.battery.capacity)
+}
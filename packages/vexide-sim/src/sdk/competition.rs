@@ -0,0 +1,26 @@
+pub use vex_sdk::competition::*;
+
+use crate::brain::with_brain;
+
+pub unsafe extern "C" fn vexCompetitionStatus() -> u32 {
+    with_brain(|brain| brain.competition_status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::brain::reset;
+
+    #[test]
+    fn status_follows_scripted_bits() {
+        reset();
+        assert_eq!(unsafe { vexCompetitionStatus() }, 0);
+
+        // Disabled autonomous while connected to a field controller.
+        with_brain(|brain| brain.competition_status = 0b1111);
+        assert_eq!(unsafe { vexCompetitionStatus() }, 0b1111);
+
+        reset();
+        assert_eq!(unsafe { vexCompetitionStatus() }, 0);
+    }
+}
@@ -0,0 +1,53 @@
+use std::ffi::CStr;
+
+pub use vex_sdk::controller::*;
+
+use crate::brain::with_brain;
+
+pub unsafe extern "C" fn vexControllerGet(id: V5_ControllerId, index: V5_ControllerIndex) -> i32 {
+    with_brain(|brain| {
+        brain
+            .controllers
+            .get(id.0 as usize)
+            .map(|controller| controller.get(index))
+            .unwrap_or_default()
+    })
+}
+
+pub unsafe extern "C" fn vexControllerConnectionStatusGet(
+    id: V5_ControllerId,
+) -> V5_ControllerStatus {
+    with_brain(|brain| {
+        brain
+            .controllers
+            .get(id.0 as usize)
+            .map(|controller| controller.connection)
+            .unwrap_or_default()
+    })
+}
+
+pub unsafe extern "C" fn vexControllerTextSet(id: u32, line: u32, col: u32, buf: *const u8) -> u32 {
+    let text = unsafe { CStr::from_ptr(buf.cast()) }.to_string_lossy();
+
+    with_brain(|brain| {
+        let Some(controller) = brain.controllers.get_mut(id as usize) else {
+            return 0;
+        };
+        let Some(line) = controller.lines.get_mut((line as usize).saturating_sub(1)) else {
+            return 0;
+        };
+
+        // Overwrite the line starting at the given column, padding it if necessary.
+        let mut chars: std::vec::Vec<char> = line.chars().collect();
+        let start = (col as usize).saturating_sub(1);
+        if chars.len() < start + text.chars().count() {
+            chars.resize(start + text.chars().count(), ' ');
+        }
+        for (i, c) in text.chars().enumerate() {
+            chars[start + i] = c;
+        }
+        *line = chars.into_iter().collect();
+
+        1
+    })
+}
@@ -0,0 +1,28 @@
+pub use vex_sdk::device::*;
+
+use crate::brain::{with_brain, DEVICE_TABLE_SIZE};
+
+pub unsafe extern "C" fn vexDevicesGetNumber() -> u32 {
+    with_brain(|brain| {
+        (1..=DEVICE_TABLE_SIZE as u8)
+            .filter(|port| brain.device(*port).is_some())
+            .count() as u32
+    })
+}
+
+pub unsafe extern "C" fn vexDevicesGetNumberByType(device_type: V5_DeviceType) -> u32 {
+    with_brain(|brain| {
+        (1..=DEVICE_TABLE_SIZE as u8)
+            .filter_map(|port| brain.device(port))
+            .filter(|device| device.device_type() == device_type)
+            .count() as u32
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGetByIndex(index: u32) -> V5_DeviceT {
+    with_brain(|brain| brain.device_handle(index))
+}
+
+pub unsafe extern "C" fn vexDeviceGetTimestamp(_device: V5_DeviceT) -> u32 {
+    with_brain(|brain| brain.clock.now().as_millis() as u32)
+}
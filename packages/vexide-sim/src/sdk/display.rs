@@ -0,0 +1,143 @@
+use core::ffi::c_char;
+use std::{ffi::CStr, string::ToString};
+
+pub use vex_sdk::display::*;
+
+use crate::{
+    brain::with_brain,
+    display::{DisplayCommand, TextKind, TextPosition},
+};
+
+fn push(command: DisplayCommand) {
+    with_brain(|brain| brain.display.push(command));
+}
+
+/// Records drawn text.
+///
+/// Format arguments are not expanded, since vexide always passes preformatted strings.
+unsafe fn push_text(position: TextPosition, kind: TextKind, format: *const c_char) {
+    let text = unsafe { CStr::from_ptr(format) }
+        .to_string_lossy()
+        .to_string();
+
+    push(DisplayCommand::Text {
+        position,
+        kind,
+        text,
+    });
+}
+
+pub unsafe extern "C" fn vexDisplayForegroundColor(col: u32) {
+    with_brain(|brain| brain.display.foreground = col);
+}
+
+pub unsafe extern "C" fn vexDisplayBackgroundColor(col: u32) {
+    with_brain(|brain| brain.display.background = col);
+}
+
+pub unsafe extern "C" fn vexDisplayErase() {
+    push(DisplayCommand::Erase);
+}
+
+pub unsafe extern "C" fn vexDisplayScroll(nStartLine: i32, nLines: i32) {
+    push(DisplayCommand::Scroll {
+        rect: (0, nStartLine, 0, 0),
+        lines: nLines,
+    });
+}
+
+pub unsafe extern "C" fn vexDisplayScrollRect(x1: i32, y1: i32, x2: i32, y2: i32, nLines: i32) {
+    push(DisplayCommand::Scroll {
+        rect: (x1, y1, x2, y2),
+        lines: nLines,
+    });
+}
+
+pub unsafe extern "C" fn vexDisplayCopyRect(
+    x1: i32,
+    y1: i32,
+    x2: i32,
+    y2: i32,
+    _pSrc: *mut u32,
+    _srcStride: i32,
+) {
+    push(DisplayCommand::CopyRect((x1, y1, x2, y2)));
+}
+
+pub unsafe extern "C" fn vexDisplayPixelSet(x: u32, y: u32) {
+    push(DisplayCommand::Pixel(x, y));
+}
+
+pub unsafe extern "C" fn vexDisplayLineDraw(x1: i32, y1: i32, x2: i32, y2: i32) {
+    push(DisplayCommand::Line((x1, y1, x2, y2)));
+}
+
+pub unsafe extern "C" fn vexDisplayRectDraw(x1: i32, y1: i32, x2: i32, y2: i32) {
+    push(DisplayCommand::RectDraw((x1, y1, x2, y2)));
+}
+
+pub unsafe extern "C" fn vexDisplayRectFill(x1: i32, y1: i32, x2: i32, y2: i32) {
+    push(DisplayCommand::RectFill((x1, y1, x2, y2)));
+}
+
+pub unsafe extern "C" fn vexDisplayCircleDraw(xc: i32, yc: i32, radius: i32) {
+    push(DisplayCommand::CircleDraw {
+        center: (xc, yc),
+        radius,
+    });
+}
+
+pub unsafe extern "C" fn vexDisplayCircleFill(xc: i32, yc: i32, radius: i32) {
+    push(DisplayCommand::CircleFill {
+        center: (xc, yc),
+        radius,
+    });
+}
+
+pub unsafe extern "C" fn vexDisplayString(nLineNumber: i32, format: *const c_char, _: ...) {
+    unsafe { push_text(TextPosition::Line(nLineNumber), TextKind::Medium, format) }
+}
+
+pub unsafe extern "C" fn vexDisplayStringAt(xpos: i32, ypos: i32, format: *const c_char, _: ...) {
+    unsafe { push_text(TextPosition::Point(xpos, ypos), TextKind::Medium, format) }
+}
+
+pub unsafe extern "C" fn vexDisplayBigString(nLineNumber: i32, format: *const c_char, _: ...) {
+    unsafe { push_text(TextPosition::Line(nLineNumber), TextKind::Big, format) }
+}
+
+pub unsafe extern "C" fn vexDisplayBigStringAt(
+    xpos: i32,
+    ypos: i32,
+    format: *const c_char,
+    _: ...
+) {
+    unsafe { push_text(TextPosition::Point(xpos, ypos), TextKind::Big, format) }
+}
+
+pub unsafe extern "C" fn vexDisplaySmallStringAt(
+    xpos: i32,
+    ypos: i32,
+    format: *const c_char,
+    _: ...
+) {
+    unsafe { push_text(TextPosition::Point(xpos, ypos), TextKind::Small, format) }
+}
+
+pub unsafe extern "C" fn vexDisplayCenteredString(nLineNumber: i32, format: *const c_char, _: ...) {
+    unsafe { push_text(TextPosition::Line(nLineNumber), TextKind::Centered, format) }
+}
+
+pub unsafe extern "C" fn vexDisplayBigCenteredString(
+    nLineNumber: i32,
+    format: *const c_char,
+    _: ...
+) {
+    unsafe {
+        push_text(
+            TextPosition::Line(nLineNumber),
+            TextKind::BigCentered,
+            format,
+        )
+    }
+}
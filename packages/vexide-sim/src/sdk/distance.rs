@@ -0,0 +1,62 @@
+use core::ffi::c_double;
+
+use vex_sdk::V5_DeviceT;
+
+use super::with_handle;
+use crate::distance::SimDistanceSensor;
+
+pub unsafe extern "C" fn vexDeviceDistanceDistanceGet(device: V5_DeviceT) -> u32 {
+    with_handle(device, |sensor: &mut SimDistanceSensor| sensor.distance)
+}
+
+pub unsafe extern "C" fn vexDeviceDistanceConfidenceGet(device: V5_DeviceT) -> u32 {
+    with_handle(device, |sensor: &mut SimDistanceSensor| sensor.confidence)
+}
+
+pub unsafe extern "C" fn vexDeviceDistanceStatusGet(device: V5_DeviceT) -> u32 {
+    with_handle(device, |sensor: &mut SimDistanceSensor| sensor.status)
+}
+
+pub unsafe extern "C" fn vexDeviceDistanceObjectSizeGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |sensor: &mut SimDistanceSensor| sensor.object_size)
+}
+
+pub unsafe extern "C" fn vexDeviceDistanceObjectVelocityGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |sensor: &mut SimDistanceSensor| {
+        sensor.object_velocity
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{brain::reset, connect, vexDeviceGetByIndex, with_device};
+
+    #[test]
+    fn readings_follow_scripted_state() {
+        reset();
+        connect(1, SimDistanceSensor::default());
+        let device = unsafe { vexDeviceGetByIndex(0) };
+
+        assert_eq!(unsafe { vexDeviceDistanceDistanceGet(device) }, 9999);
+
+        with_device(1, |sensor: &mut SimDistanceSensor| {
+            sensor.distance = 250;
+            sensor.confidence = 63;
+            sensor.object_velocity = -0.5;
+        });
+
+        assert_eq!(unsafe { vexDeviceDistanceDistanceGet(device) }, 250);
+        assert_eq!(unsafe { vexDeviceDistanceConfidenceGet(device) }, 63);
+        assert_eq!(unsafe { vexDeviceDistanceObjectVelocityGet(device) }, -0.5);
+    }
+
+    #[test]
+    fn readings_from_other_devices_are_ignored() {
+        reset();
+        connect(1, crate::motor::SimMotor::default());
+        let device = unsafe { vexDeviceGetByIndex(0) };
+
+        assert_eq!(unsafe { vexDeviceDistanceDistanceGet(device) }, 0);
+    }
+}
@@ -0,0 +1,7 @@
+pub use vex_sdk::file::*;
+
+use crate::brain::with_brain;
+
+pub unsafe extern "C" fn vexFileDriveStatus(_drive: u32) -> bool {
+    with_brain(|brain| brain.sd_card_inserted)
+}
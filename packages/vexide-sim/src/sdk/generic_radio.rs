@@ -0,0 +1,69 @@
+use core::ffi::{c_char, c_int};
+use std::{ffi::CStr, string::ToString};
+
+use vex_sdk::V5_DeviceT;
+
+use super::with_handle;
+use crate::serial::SimRadio;
+
+pub unsafe extern "C" fn vexDeviceGenericRadioConnection(
+    device: V5_DeviceT,
+    link_id: *mut c_char,
+    r#type: c_int,
+    ov: bool,
+) {
+    let link_id = (!link_id.is_null()).then(|| {
+        unsafe { CStr::from_ptr(link_id) }
+            .to_string_lossy()
+            .to_string()
+    });
+
+    with_handle(device, |radio: &mut SimRadio| {
+        radio.link_id = link_id;
+        radio.link_type = r#type;
+        radio.link_override = ov;
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGenericRadioWriteFree(device: V5_DeviceT) -> i32 {
+    with_handle(device, |radio: &mut SimRadio| {
+        SimRadio::BUFFER_SIZE.saturating_sub(radio.tx.len()) as i32
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGenericRadioTransmit(
+    device: V5_DeviceT,
+    data: *const u8,
+    size: u16,
+) -> i32 {
+    let data = unsafe { core::slice::from_raw_parts(data, size as usize) };
+
+    with_handle(device, |radio: &mut SimRadio| {
+        radio.tx.extend_from_slice(data);
+        size as i32
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGenericRadioReceiveAvail(device: V5_DeviceT) -> u32 {
+    with_handle(device, |radio: &mut SimRadio| radio.rx.len() as u32)
+}
+
+pub unsafe extern "C" fn vexDeviceGenericRadioReceive(
+    device: V5_DeviceT,
+    data: *mut u8,
+    size: u16,
+) -> i32 {
+    let data = unsafe { core::slice::from_raw_parts_mut(data, size as usize) };
+
+    with_handle(device, |radio: &mut SimRadio| {
+        let received = data.len().min(radio.rx.len());
+        for (byte, value) in data.iter_mut().zip(radio.rx.drain(..received)) {
+            *byte = value;
+        }
+        received as i32
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGenericRadioLinkStatus(device: V5_DeviceT) -> bool {
+    with_handle(device, |radio: &mut SimRadio| radio.linked)
+}
@@ -0,0 +1,132 @@
+use vex_sdk::V5_DeviceT;
+
+use super::with_handle;
+use crate::serial::SimSerialPort;
+
+pub unsafe extern "C" fn vexDeviceGenericSerialEnable(device: V5_DeviceT, _options: i32) {
+    with_handle(device, |serial: &mut SimSerialPort| serial.enabled = true)
+}
+
+pub unsafe extern "C" fn vexDeviceGenericSerialBaudrate(device: V5_DeviceT, baudrate: i32) {
+    with_handle(device, |serial: &mut SimSerialPort| {
+        serial.baud_rate = baudrate
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGenericSerialWriteChar(device: V5_DeviceT, c: u8) -> i32 {
+    with_handle(device, |serial: &mut SimSerialPort| {
+        serial.tx.push(c);
+        1
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGenericSerialWriteFree(device: V5_DeviceT) -> i32 {
+    with_handle(device, |serial: &mut SimSerialPort| {
+        serial.tx_capacity as i32
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGenericSerialTransmit(
+    device: V5_DeviceT,
+    buffer: *const u8,
+    length: i32,
+) -> i32 {
+    let buffer = unsafe { core::slice::from_raw_parts(buffer, length as usize) };
+
+    with_handle(device, |serial: &mut SimSerialPort| {
+        serial.tx.extend_from_slice(buffer);
+        length
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGenericSerialReadChar(device: V5_DeviceT) -> i32 {
+    with_handle(device, |serial: &mut SimSerialPort| {
+        serial.rx.pop_front().map_or(-1, i32::from)
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGenericSerialPeekChar(device: V5_DeviceT) -> i32 {
+    with_handle(device, |serial: &mut SimSerialPort| {
+        serial.rx.front().map_or(-1, |byte| i32::from(*byte))
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGenericSerialReceiveAvail(device: V5_DeviceT) -> i32 {
+    with_handle(device, |serial: &mut SimSerialPort| serial.rx.len() as i32)
+}
+
+pub unsafe extern "C" fn vexDeviceGenericSerialReceive(
+    device: V5_DeviceT,
+    buffer: *mut u8,
+    length: i32,
+) -> i32 {
+    let buffer = unsafe { core::slice::from_raw_parts_mut(buffer, length as usize) };
+
+    with_handle(device, |serial: &mut SimSerialPort| {
+        let received = buffer.len().min(serial.rx.len());
+        for (byte, value) in buffer.iter_mut().zip(serial.rx.drain(..received)) {
+            *byte = value;
+        }
+        received as i32
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceGenericSerialFlush(device: V5_DeviceT) {
+    with_handle(device, |serial: &mut SimSerialPort| {
+        serial.rx.clear();
+        serial.tx.clear();
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{brain::reset, connect, vexDeviceGetByIndex, with_device};
+
+    #[test]
+    fn receive_drains_rx_in_order() {
+        reset();
+        connect(1, SimSerialPort::default());
+        let device = unsafe { vexDeviceGetByIndex(0) };
+
+        with_device(1, |serial: &mut SimSerialPort| {
+            serial.rx.extend([1, 2, 3, 4, 5])
+        });
+
+        assert_eq!(unsafe { vexDeviceGenericSerialPeekChar(device) }, 1);
+        assert_eq!(unsafe { vexDeviceGenericSerialReadChar(device) }, 1);
+
+        let mut buffer = [0; 3];
+        let received = unsafe {
+            vexDeviceGenericSerialReceive(device, buffer.as_mut_ptr(), buffer.len() as _)
+        };
+        assert_eq!(received, 3);
+        assert_eq!(buffer, [2, 3, 4]);
+
+        assert_eq!(unsafe { vexDeviceGenericSerialReceiveAvail(device) }, 1);
+        assert_eq!(unsafe { vexDeviceGenericSerialReadChar(device) }, 5);
+        assert_eq!(unsafe { vexDeviceGenericSerialReadChar(device) }, -1);
+    }
+
+    #[test]
+    fn transmit_appends_to_tx() {
+        reset();
+        connect(1, SimSerialPort::default());
+        let device = unsafe { vexDeviceGetByIndex(0) };
+
+        unsafe {
+            vexDeviceGenericSerialWriteChar(device, b'a');
+            vexDeviceGenericSerialTransmit(device, b"bc".as_ptr(), 2);
+        }
+        assert_eq!(
+            with_device(1, |serial: &mut SimSerialPort| serial.tx.clone()),
+            Some(b"abc".to_vec())
+        );
+
+        unsafe { vexDeviceGenericSerialFlush(device) };
+        assert_eq!(
+            with_device(1, |serial: &mut SimSerialPort| serial.tx.is_empty()),
+            Some(true)
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use core::ffi::c_double;
+
+pub use vex_sdk::imu::*;
+use vex_sdk::V5_DeviceT;
+
+use super::with_handle;
+use crate::imu::SimInertialSensor;
+
+pub unsafe extern "C" fn vexDeviceImuReset(device: V5_DeviceT) {
+    with_handle(device, SimInertialSensor::start_calibration)
+}
+
+pub unsafe extern "C" fn vexDeviceImuHeadingGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |imu: &mut SimInertialSensor| imu.heading)
+}
+
+pub unsafe extern "C" fn vexDeviceImuDegreesGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |imu: &mut SimInertialSensor| imu.degrees)
+}
+
+pub unsafe extern "C" fn vexDeviceImuQuaternionGet(
+    device: V5_DeviceT,
+    data: *mut V5_DeviceImuQuaternion,
+) {
+    let quaternion = with_handle(device, |imu: &mut SimInertialSensor| imu.quaternion);
+    unsafe { data.write(quaternion) }
+}
+
+pub unsafe extern "C" fn vexDeviceImuAttitudeGet(
+    device: V5_DeviceT,
+    data: *mut V5_DeviceImuAttitude,
+) {
+    let attitude = with_handle(device, |imu: &mut SimInertialSensor| imu.attitude);
+    unsafe { data.write(attitude) }
+}
+
+pub unsafe extern "C" fn vexDeviceImuRawGyroGet(device: V5_DeviceT, data: *mut V5_DeviceImuRaw) {
+    let gyro = with_handle(device, |imu: &mut SimInertialSensor| imu.gyro);
+    unsafe { data.write(gyro) }
+}
+
+pub unsafe extern "C" fn vexDeviceImuRawAccelGet(device: V5_DeviceT, data: *mut V5_DeviceImuRaw) {
+    let accel = with_handle(device, |imu: &mut SimInertialSensor| imu.accel);
+    unsafe { data.write(accel) }
+}
+
+pub unsafe extern "C" fn vexDeviceImuStatusGet(device: V5_DeviceT) -> u32 {
+    with_handle(device, SimInertialSensor::read_status)
+}
+
+pub unsafe extern "C" fn vexDeviceImuDataRateSet(device: V5_DeviceT, rate: u32) {
+    with_handle(device, |imu: &mut SimInertialSensor| imu.data_rate = rate)
+}
@@ -0,0 +1,78 @@
+//! Simulated implementations of the VEX SDK functions used by vexide.
+//!
+//! Each module mirrors a module of [`vex_sdk`], re-exporting its types and replacing the jump
+//! table functions used by vexide with implementations backed by the simulated [`Brain`]. The
+//! functions are documented in [`vex_sdk`].
+//!
+//! SDK functions that vexide never calls are re-exported unchanged, and will crash if called on
+//! the host.
+//!
+//! [`Brain`]: crate::Brain
+
+#![allow(
+    missing_docs,
+    non_snake_case,
+    clippy::missing_safety_doc,
+    clippy::missing_const_for_fn
+)]
+
+mod abs_enc;
+mod adi;
+mod battery;
+mod competition;
+mod controller;
+mod device;
+mod display;
+mod distance;
+mod file;
+mod generic_radio;
+mod generic_serial;
+mod imu;
+mod motor;
+mod optical;
+mod serial;
+mod system;
+mod task;
+mod touch;
+mod vision;
+
+pub use abs_enc::*;
+pub use adi::*;
+pub use battery::*;
+pub use competition::*;
+pub use controller::*;
+pub use device::*;
+pub use display::*;
+pub use distance::*;
+pub use file::*;
+pub use generic_radio::*;
+pub use generic_serial::*;
+pub use imu::*;
+pub use motor::*;
+pub use optical::*;
+pub use serial::*;
+pub use system::*;
+pub use task::*;
+pub use touch::*;
+pub use vex_sdk::{
+    ai_vision::*, arm::*, gps::*, led::*, light_tower::*, magnet::*, pneumatic::*, range::*,
+    JUMP_TABLE_START,
+};
+pub use vision::*;
+
+use crate::{brain::with_brain, device::SimSmartDevice};
+
+/// Runs a closure with the simulated device of type `T` behind a raw SDK device handle.
+///
+/// Like VEXos, calls on a port that doesn't hold a matching device are ignored, returning
+/// the default value of `R`.
+pub(crate) fn with_handle<T: SimSmartDevice, R: Default>(
+    device: vex_sdk::V5_DeviceT,
+    f: impl FnOnce(&mut T) -> R,
+) -> R {
+    // SAFETY: Device handles are only ever created by `vexDeviceGetByIndex`, which points
+    // them into the brain's device table.
+    let port = unsafe { (*device).one_indexed_port };
+
+    with_brain(|brain| brain.get_mut(port).map(f).unwrap_or_default())
+}
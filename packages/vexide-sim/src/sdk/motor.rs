@@ -0,0 +1,173 @@
+use core::ffi::c_double;
+
+pub use vex_sdk::motor::*;
+use vex_sdk::V5_DeviceT;
+
+use super::with_handle;
+use crate::{
+    brain::with_brain,
+    motor::{MotorCommand, SimMotor},
+};
+
+pub unsafe extern "C" fn vexDeviceMotorVelocitySet(device: V5_DeviceT, velocity: i32) {
+    with_handle(device, |motor: &mut SimMotor| {
+        motor.command = MotorCommand::Velocity(velocity)
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceMotorVelocityGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |motor: &mut SimMotor| motor.velocity)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorVelocityUpdate(device: V5_DeviceT, velocity: i32) {
+    with_handle(device, |motor: &mut SimMotor| {
+        if let MotorCommand::AbsoluteTarget { position, .. } = motor.command {
+            motor.command = MotorCommand::AbsoluteTarget { position, velocity };
+        }
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceMotorVoltageSet(device: V5_DeviceT, voltage: i32) {
+    with_handle(device, |motor: &mut SimMotor| {
+        motor.command = MotorCommand::Voltage(voltage)
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceMotorVoltageGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |motor: &mut SimMotor| motor.voltage)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorAbsoluteTargetSet(
+    device: V5_DeviceT,
+    position: c_double,
+    veloctiy: i32,
+) {
+    with_handle(device, |motor: &mut SimMotor| {
+        motor.command = MotorCommand::AbsoluteTarget {
+            position,
+            velocity: veloctiy,
+        }
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceMotorCurrentLimitSet(device: V5_DeviceT, limit: i32) {
+    with_handle(device, |motor: &mut SimMotor| motor.current_limit = limit)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorCurrentLimitGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |motor: &mut SimMotor| motor.current_limit)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorVoltageLimitSet(device: V5_DeviceT, limit: i32) {
+    with_handle(device, |motor: &mut SimMotor| motor.voltage_limit = limit)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorVoltageLimitGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |motor: &mut SimMotor| motor.voltage_limit)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorCurrentGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |motor: &mut SimMotor| motor.current)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorPowerGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |motor: &mut SimMotor| motor.power)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorTorqueGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |motor: &mut SimMotor| motor.torque)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorEfficiencyGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |motor: &mut SimMotor| motor.efficiency)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorTemperatureGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |motor: &mut SimMotor| motor.temperature)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorFaultsGet(device: V5_DeviceT) -> u32 {
+    with_handle(device, |motor: &mut SimMotor| motor.faults)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorFlagsGet(device: V5_DeviceT) -> u32 {
+    with_handle(device, |motor: &mut SimMotor| motor.flags)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorReverseFlagSet(device: V5_DeviceT, reverse: bool) {
+    with_handle(device, |motor: &mut SimMotor| motor.reversed = reverse)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorReverseFlagGet(device: V5_DeviceT) -> bool {
+    with_handle(device, |motor: &mut SimMotor| motor.reversed)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorEncoderUnitsSet(
+    device: V5_DeviceT,
+    units: V5MotorEncoderUnits,
+) {
+    with_handle(device, |motor: &mut SimMotor| motor.encoder_units = units)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorBrakeModeSet(device: V5_DeviceT, mode: V5MotorBrakeMode) {
+    with_handle(device, |motor: &mut SimMotor| motor.brake_mode = mode)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorGearingSet(device: V5_DeviceT, gearset: V5MotorGearset) {
+    with_handle(device, |motor: &mut SimMotor| motor.gearset = gearset)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorGearingGet(device: V5_DeviceT) -> V5MotorGearset {
+    with_handle(device, |motor: &mut SimMotor| motor.gearset)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorPositionSet(device: V5_DeviceT, position: c_double) {
+    with_handle(device, |motor: &mut SimMotor| motor.position = position)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorPositionGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |motor: &mut SimMotor| motor.position)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorPositionReset(device: V5_DeviceT) {
+    with_handle(device, |motor: &mut SimMotor| motor.position = 0.0)
+}
+
+pub unsafe extern "C" fn vexDeviceMotorPositionRawGet(
+    device: V5_DeviceT,
+    timestamp: *mut u32,
+) -> i32 {
+    let now = with_brain(|brain| brain.clock.now().as_millis() as u32);
+    let (ticks, time) = with_handle(device, |motor: &mut SimMotor| {
+        (
+            motor.raw_position,
+            motor.raw_position_timestamp.unwrap_or(now),
+        )
+    });
+
+    if !timestamp.is_null() {
+        unsafe { timestamp.write(time) }
+    }
+
+    ticks
+}
+
+pub unsafe extern "C" fn vexDeviceMotorPositionPidSet(
+    device: V5_DeviceT,
+    pid: *mut V5_DeviceMotorPid,
+) {
+    let pid = unsafe { pid.read() };
+    with_handle(device, |motor: &mut SimMotor| {
+        motor.position_pid = Some(pid)
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceMotorVelocityPidSet(
+    device: V5_DeviceT,
+    pid: *mut V5_DeviceMotorPid,
+) {
+    let pid = unsafe { pid.read() };
+    with_handle(device, |motor: &mut SimMotor| {
+        motor.velocity_pid = Some(pid)
+    })
+}
@@ -0,0 +1,88 @@
+use core::ffi::c_double;
+
+pub use vex_sdk::optical::*;
+use vex_sdk::V5_DeviceT;
+
+use super::with_handle;
+use crate::optical::SimOpticalSensor;
+
+pub unsafe extern "C" fn vexDeviceOpticalHueGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |sensor: &mut SimOpticalSensor| sensor.hue)
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalSatGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |sensor: &mut SimOpticalSensor| sensor.saturation)
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalBrightnessGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |sensor: &mut SimOpticalSensor| sensor.brightness)
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalProximityGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |sensor: &mut SimOpticalSensor| sensor.proximity)
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalRgbGet(
+    device: V5_DeviceT,
+    data: *mut V5_DeviceOpticalRgb,
+) {
+    let rgb = with_handle(device, |sensor: &mut SimOpticalSensor| sensor.rgb);
+    unsafe { data.write(rgb) }
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalRawGet(
+    device: V5_DeviceT,
+    data: *mut V5_DeviceOpticalRaw,
+) {
+    let raw = with_handle(device, |sensor: &mut SimOpticalSensor| sensor.raw);
+    unsafe { data.write(raw) }
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalLedPwmSet(device: V5_DeviceT, value: i32) {
+    with_handle(device, |sensor: &mut SimOpticalSensor| {
+        sensor.led_pwm = value
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalLedPwmGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |sensor: &mut SimOpticalSensor| sensor.led_pwm)
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalStatusGet(device: V5_DeviceT) -> u32 {
+    with_handle(device, |sensor: &mut SimOpticalSensor| sensor.status)
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalGestureGet(
+    device: V5_DeviceT,
+    pData: *mut V5_DeviceOpticalGesture,
+) -> u32 {
+    let gesture = with_handle(device, |sensor: &mut SimOpticalSensor| sensor.gesture);
+    if !pData.is_null() {
+        unsafe { pData.write(gesture) }
+    }
+    gesture.gesture_type as u32
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalGestureEnable(device: V5_DeviceT) {
+    with_handle(device, |sensor: &mut SimOpticalSensor| {
+        sensor.gestures_enabled = true
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalGestureDisable(device: V5_DeviceT) {
+    with_handle(device, |sensor: &mut SimOpticalSensor| {
+        sensor.gestures_enabled = false
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalIntegrationTimeSet(device: V5_DeviceT, timeMs: c_double) {
+    with_handle(device, |sensor: &mut SimOpticalSensor| {
+        sensor.integration_time = timeMs
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceOpticalIntegrationTimeGet(device: V5_DeviceT) -> c_double {
+    with_handle(device, |sensor: &mut SimOpticalSensor| {
+        sensor.integration_time
+    })
+}
@@ -0,0 +1,25 @@
+pub use vex_sdk::serial::*;
+
+use crate::brain::with_brain;
+
+/// The serial channel used for the user program's stdio.
+const STDIO_CHANNEL: u32 = 1;
+
+pub unsafe extern "C" fn vexSerialWriteBuffer(channel: u32, data: *const u8, data_len: u32) -> i32 {
+    if channel != STDIO_CHANNEL {
+        return -1;
+    }
+
+    let data = unsafe { core::slice::from_raw_parts(data, data_len as usize) };
+    with_brain(|brain| brain.stdout.extend_from_slice(data));
+
+    data_len as i32
+}
+
+pub unsafe extern "C" fn vexSerialReadChar(channel: u32) -> i32 {
+    if channel != STDIO_CHANNEL {
+        return -1;
+    }
+
+    with_brain(|brain| brain.stdin.pop_front().map_or(-1, i32::from))
+}
@@ -0,0 +1,11 @@
+pub use vex_sdk::system::*;
+
+use crate::brain::with_brain;
+
+pub unsafe extern "C" fn vexSystemHighResTimeGet() -> u64 {
+    with_brain(|brain| brain.clock.now().as_micros() as u64)
+}
+
+pub unsafe extern "C" fn vexSystemExitRequest() {
+    with_brain(|brain| brain.exit_requested = true);
+}
@@ -0,0 +1,4 @@
+pub use vex_sdk::task::*;
+
+/// Background processing is a no-op in the simulator, since all state is updated immediately.
+pub unsafe extern "C" fn vexTasksRun() {}
@@ -0,0 +1,8 @@
+pub use vex_sdk::touch::*;
+
+use crate::brain::with_brain;
+
+pub unsafe extern "C" fn vexTouchDataGet(status: *mut V5_TouchStatus) {
+    let touch = with_brain(|brain| brain.touch);
+    unsafe { status.write(touch) }
+}
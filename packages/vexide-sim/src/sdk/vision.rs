@@ -0,0 +1,143 @@
+pub use vex_sdk::vision::*;
+use vex_sdk::V5_DeviceT;
+
+use super::with_handle;
+use crate::vision::SimVisionSensor;
+
+pub unsafe extern "C" fn vexDeviceVisionModeSet(device: V5_DeviceT, mode: V5VisionMode) {
+    with_handle(device, |sensor: &mut SimVisionSensor| sensor.mode = mode)
+}
+
+pub unsafe extern "C" fn vexDeviceVisionModeGet(device: V5_DeviceT) -> V5VisionMode {
+    with_handle(device, |sensor: &mut SimVisionSensor| sensor.mode)
+}
+
+pub unsafe extern "C" fn vexDeviceVisionObjectCountGet(device: V5_DeviceT) -> i32 {
+    with_handle(device, |sensor: &mut SimVisionSensor| {
+        sensor.objects.len() as i32
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceVisionObjectGet(
+    device: V5_DeviceT,
+    index: u32,
+    object: *mut V5_DeviceVisionObject,
+) -> i32 {
+    let found = with_handle(device, |sensor: &mut SimVisionSensor| {
+        sensor.objects.get(index as usize).copied()
+    });
+
+    match found {
+        Some(found) => {
+            unsafe { object.write(found) }
+            1
+        }
+        None => 0,
+    }
+}
+
+pub unsafe extern "C" fn vexDeviceVisionSignatureSet(
+    device: V5_DeviceT,
+    signature: *mut V5_DeviceVisionSignature,
+) {
+    let signature = unsafe { signature.read() };
+
+    with_handle(device, |sensor: &mut SimVisionSensor| {
+        if let Some(slot) = sensor
+            .signatures
+            .get_mut((signature.id as usize).wrapping_sub(1))
+        {
+            *slot = Some(signature);
+        }
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceVisionSignatureGet(
+    device: V5_DeviceT,
+    id: u32,
+    signature: *mut V5_DeviceVisionSignature,
+) -> bool {
+    let found = with_handle(device, |sensor: &mut SimVisionSensor| {
+        sensor
+            .signatures
+            .get((id as usize).wrapping_sub(1))
+            .copied()
+            .flatten()
+    });
+
+    match found {
+        Some(found) => {
+            unsafe { signature.write(found) }
+            true
+        }
+        None => false,
+    }
+}
+
+pub unsafe extern "C" fn vexDeviceVisionBrightnessSet(device: V5_DeviceT, percent: u8) {
+    with_handle(device, |sensor: &mut SimVisionSensor| {
+        sensor.brightness = percent
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceVisionBrightnessGet(device: V5_DeviceT) -> u8 {
+    with_handle(device, |sensor: &mut SimVisionSensor| sensor.brightness)
+}
+
+pub unsafe extern "C" fn vexDeviceVisionWhiteBalanceModeSet(
+    device: V5_DeviceT,
+    mode: V5VisionWBMode,
+) {
+    with_handle(device, |sensor: &mut SimVisionSensor| {
+        sensor.white_balance_mode = mode
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceVisionWhiteBalanceModeGet(device: V5_DeviceT) -> V5VisionWBMode {
+    with_handle(device, |sensor: &mut SimVisionSensor| {
+        sensor.white_balance_mode
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceVisionWhiteBalanceSet(
+    device: V5_DeviceT,
+    color: V5_DeviceVisionRgb,
+) {
+    with_handle(device, |sensor: &mut SimVisionSensor| {
+        sensor.white_balance = color
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceVisionWhiteBalanceGet(device: V5_DeviceT) -> V5_DeviceVisionRgb {
+    with_handle(device, |sensor: &mut SimVisionSensor| sensor.white_balance)
+}
+
+pub unsafe extern "C" fn vexDeviceVisionLedModeSet(device: V5_DeviceT, mode: V5VisionLedMode) {
+    with_handle(device, |sensor: &mut SimVisionSensor| {
+        sensor.led_mode = mode
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceVisionLedModeGet(device: V5_DeviceT) -> V5VisionLedMode {
+    with_handle(device, |sensor: &mut SimVisionSensor| sensor.led_mode)
+}
+
+pub unsafe extern "C" fn vexDeviceVisionLedColorSet(device: V5_DeviceT, color: V5_DeviceVisionRgb) {
+    with_handle(device, |sensor: &mut SimVisionSensor| {
+        sensor.led_color = color
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceVisionLedColorGet(device: V5_DeviceT) -> V5_DeviceVisionRgb {
+    with_handle(device, |sensor: &mut SimVisionSensor| sensor.led_color)
+}
+
+pub unsafe extern "C" fn vexDeviceVisionWifiModeSet(device: V5_DeviceT, mode: V5VisionWifiMode) {
+    with_handle(device, |sensor: &mut SimVisionSensor| {
+        sensor.wifi_mode = mode
+    })
+}
+
+pub unsafe extern "C" fn vexDeviceVisionWifiModeGet(device: V5_DeviceT) -> V5VisionWifiMode {
+    with_handle(device, |sensor: &mut SimVisionSensor| sensor.wifi_mode)
+}
@@ -0,0 +1,57 @@
+//! Simulated serial devices.
+
+use std::{collections::VecDeque, string::String, vec::Vec};
+
+/// State of a smart port configured for generic serial communication.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimSerialPort {
+    /// Whether the port has been enabled for generic serial.
+    pub enabled: bool,
+    /// The configured baud rate.
+    pub baud_rate: i32,
+    /// Bytes waiting to be read by vexide.
+    pub rx: VecDeque<u8>,
+    /// Bytes written by vexide.
+    pub tx: Vec<u8>,
+    /// Size of the output buffer reported as free space.
+    pub tx_capacity: usize,
+}
+
+impl SimSerialPort {
+    /// The size of the VEXos generic serial FIFO buffers.
+    pub const BUFFER_SIZE: usize = 1024;
+}
+
+impl Default for SimSerialPort {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            baud_rate: 0,
+            rx: VecDeque::new(),
+            tx: Vec::new(),
+            tx_capacity: Self::BUFFER_SIZE,
+        }
+    }
+}
+
+/// State of a simulated V5 radio used for VEXlink.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SimRadio {
+    /// The link ID passed when opening the connection, if any.
+    pub link_id: Option<String>,
+    /// The raw link type passed when opening the connection.
+    pub link_type: i32,
+    /// Whether the link was opened as an override of a controller connection.
+    pub link_override: bool,
+    /// Whether the link to the other radio is established.
+    pub linked: bool,
+    /// Bytes waiting to be read by vexide.
+    pub rx: VecDeque<u8>,
+    /// Bytes written by vexide.
+    pub tx: Vec<u8>,
+}
+
+impl SimRadio {
+    /// Size of the radio's output buffer.
+    pub const BUFFER_SIZE: usize = 512;
+}
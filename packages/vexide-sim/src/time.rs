@@ -0,0 +1,90 @@
+//! Control over the simulated system clock.
+//!
+//! The clock follows real time by default. It can be paused to make tests deterministic, in
+//! which case time only moves forward when [`advance`] is called.
+
+use core::time::Duration;
+
+use crate::brain::with_brain;
+
+/// The simulated system clock returned by `vexSystemHighResTimeGet`.
+#[derive(Debug, Clone, Copy)]
+pub struct Clock {
+    /// Time elapsed at the last point the clock was paused, resumed or advanced.
+    base: Duration,
+    /// The real time at which the clock was last resumed, or `None` if the clock is paused.
+    resumed_at: Option<std::time::Instant>,
+}
+
+impl Clock {
+    /// Creates a running clock starting at zero.
+    pub fn new() -> Self {
+        Self {
+            base: Duration::ZERO,
+            resumed_at: Some(std::time::Instant::now()),
+        }
+    }
+
+    /// Time elapsed since the start of the simulated program.
+    pub fn now(&self) -> Duration {
+        match self.resumed_at {
+            Some(resumed_at) => self.base + resumed_at.elapsed(),
+            None => self.base,
+        }
+    }
+
+    /// Stops the clock from following real time.
+    pub fn pause(&mut self) {
+        self.base = self.now();
+        self.resumed_at = None;
+    }
+
+    /// Makes the clock follow real time again.
+    pub fn resume(&mut self) {
+        if self.resumed_at.is_none() {
+            self.resumed_at = Some(std::time::Instant::now());
+        }
+    }
+
+    /// Returns `true` if the clock is paused.
+    pub const fn is_paused(&self) -> bool {
+        self.resumed_at.is_none()
+    }
+
+    /// Moves the clock forward by the given duration.
+    pub fn advance(&mut self, duration: Duration) {
+        self.base += duration;
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Time elapsed since the start of the simulated program on the current thread's brain.
+pub fn now() -> Duration {
+    with_brain(|brain| brain.clock.now())
+}
+
+/// Pauses the current thread's simulated clock.
+///
+/// See [`Clock::pause`].
+pub fn pause() {
+    with_brain(|brain| brain.clock.pause());
+}
+
+/// Resumes the current thread's simulated clock.
+///
+/// See [`Clock::resume`].
+pub fn resume() {
+    with_brain(|brain| brain.clock.resume());
+}
+
+/// Moves the current thread's simulated clock forward by the given duration.
+///
+/// See [`Clock::advance`].
+pub fn advance(duration: Duration) {
+    with_brain(|brain| brain.clock.advance(duration));
+}
@@ -0,0 +1,31 @@
+//! Simulated V5 vision sensors.
+
+use std::vec::Vec;
+
+use vex_sdk::{
+    V5VisionLedMode, V5VisionMode, V5VisionWBMode, V5VisionWifiMode, V5_DeviceVisionObject,
+    V5_DeviceVisionRgb, V5_DeviceVisionSignature,
+};
+
+/// State of a simulated V5 vision sensor.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SimVisionSensor {
+    /// The configured detection mode.
+    pub mode: V5VisionMode,
+    /// Objects currently detected by the sensor, ordered from largest to smallest.
+    pub objects: Vec<V5_DeviceVisionObject>,
+    /// Signatures stored on the sensor, indexed by their ID minus one.
+    pub signatures: [Option<V5_DeviceVisionSignature>; 7],
+    /// Camera brightness as a percentage.
+    pub brightness: u8,
+    /// The configured white balance mode.
+    pub white_balance_mode: V5VisionWBMode,
+    /// The configured manual white balance.
+    pub white_balance: V5_DeviceVisionRgb,
+    /// The configured LED mode.
+    pub led_mode: V5VisionLedMode,
+    /// The configured LED color.
+    pub led_color: V5_DeviceVisionRgb,
+    /// The configured wifi mode.
+    pub wifi_mode: V5VisionWifiMode,
+}
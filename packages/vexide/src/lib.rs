@@ -71,7 +71,7 @@ pub use vexide_panic as panic;
 /// This module is meant to be glob imported.
 pub mod prelude {
     #[cfg(feature = "async")]
-    pub use vexide_async::{block_on, sleep, spawn};
+    pub use vexide_async::{block_on, sleep, sleep_until, spawn};
     #[cfg(feature = "core")]
     pub use vexide_core::{
         dbg,
@@ -87,9 +87,10 @@ pub mod prelude {
             AdiDevice, AdiPort,
         },
         color::Rgb,
+        competition::{Compete, CompeteExt},
         controller::Controller,
         peripherals::{DynamicPeripherals, Peripherals},
-        position::Position,
+        position::{Gearing, Position},
         screen::{Circle, Line, Rect, Screen, Text, TextFormat, TextPosition, TouchState},
         smart::{
             distance::DistanceSensor,